@@ -1,14 +1,15 @@
 use std::collections::HashSet;
 
 use anyhow::{anyhow, Context as _};
-use sqlparser::ast::{Expr, Ident, TableFactor, TableWithJoins, With};
+use sqlparser::ast::{Expr, Ident, Statement, TableFactor, TableWithJoins, With};
 
 use crate::{
     expr::visit_expr,
+    ident::parse_sql,
     nullable::{Nullable, NullableResult},
-    source::Source,
+    source::{Source, View, ViewQuery},
     wal::{Wal, WalEntry},
-    SqlFlavour, Table, TableColumn, TableId, Tables,
+    SqlFlavour, Table, TableColumn, Tables,
 };
 
 pub struct Context {
@@ -16,22 +17,89 @@ pub struct Context {
     pub source: Source,
     pub wal: Wal,
     pub flavour: SqlFlavour,
+    /// Set while visiting the projection of a select whose `WHERE` is never true.
+    pub empty_selection: bool,
+    /// The number of tables of the catalog, the tables after it are ctes.
+    catalog_len: usize,
+    /// The views whose query is being analysed, a view can't reference itself.
+    resolving_views: Vec<Vec<Ident>>,
+    /// The tables of the views that were used, by the name of the view.
+    view_tables: Vec<(Vec<Ident>, Table)>,
 }
 
 impl Context {
     pub fn new(tables: Tables, source: Source, wal: Wal, flavour: SqlFlavour) -> Context {
         Self {
             tables,
+            catalog_len: source.len(),
+            resolving_views: Vec::new(),
+            view_tables: Vec::new(),
             source,
             wal,
             flavour,
+            empty_selection: false,
         }
     }
 
+    /// Finds a table or a view of the source.
+    pub fn source_table(&mut self, name: &[Ident]) -> anyhow::Result<Table> {
+        if name.len() > 2 {
+            return Err(anyhow!("names qualified by a catalog are not supported: {name:?}"));
+        }
+        if let Some(table) = self.source.find_table(name, self.flavour, self.catalog_len) {
+            return Ok(table);
+        }
+        let view = self
+            .source
+            .find_view(name, self.flavour)
+            .context(format!("could not find table by original name: {name:?}"))?
+            .clone();
+        let mut table = self.view_table(&view)?;
+        table.schema.get_or_insert(self.flavour.default_schema());
+        Ok(table)
+    }
+
+    /// The table of a view, the query of a view is analysed once by a context.
+    pub fn view_table(&mut self, view: &View) -> anyhow::Result<Table> {
+        if let Some((_, table)) = self.view_tables.iter().find(|(name, _)| *name == view.name) {
+            return Ok(table.clone());
+        }
+
+        if self.resolving_views.contains(&view.name) {
+            return Err(anyhow!("view references itself: {:?}", view.name));
+        }
+
+        let parsed;
+        let query = match &view.query {
+            ViewQuery::Sql(sql) => {
+                parsed = parse_sql(self.flavour.to_dialect(), sql, self.flavour)?;
+                match parsed.first() {
+                    Some(Statement::Query(query)) => query,
+                    statement => return Err(anyhow!("expected a query, found: {statement:?}")),
+                }
+            }
+            ViewQuery::Parsed(query) => query,
+        };
+
+        // the ctes of the query using the view aren't visible in the view.
+        let mut source = self.source.clone();
+        source.truncate(self.catalog_len);
+        let mut context = Context::new(Tables::new(), source, Wal::new(), self.flavour);
+        context.resolving_views = self.resolving_views.clone();
+        context.resolving_views.push(view.name.clone());
+        context.view_tables = std::mem::take(&mut self.view_tables);
+        let nullable = context.nullable_for(query.as_ref());
+        self.view_tables = context.view_tables;
+
+        let table = table_of_view(&view.name, &view.columns, nullable?.flatten());
+        self.view_tables.push((view.name.clone(), table.clone()));
+        Ok(table)
+    }
+
     pub fn add_active_tables(&mut self, table: &TableWithJoins) -> anyhow::Result<()> {
-        self.visit_table_factor(&&table.relation)?;
+        self.visit_table_factor(&table.relation)?;
         for join_table in &table.joins {
-            self.visit_table_factor(&&join_table.relation)?;
+            self.visit_table_factor(&join_table.relation)?;
         }
         Ok(())
     }
@@ -39,10 +107,7 @@ impl Context {
     pub fn visit_table_factor(&mut self, table: &TableFactor) -> anyhow::Result<()> {
         match table {
             TableFactor::Table { name, alias, .. } => {
-                let mut table = self
-                    .source
-                    .find_by_original_name(&name.0)
-                    .context(format!("could not find table by original name: {name:?}"))?;
+                let mut table = self.source_table(&name.0)?;
                 table.add_alias(alias);
                 self.push(table);
                 Ok(())
@@ -77,11 +142,17 @@ impl Context {
                 let nullable = {
                     let results: Vec<_> = array_exprs
                         .iter()
-                        .map(|expr| visit_expr(expr, None, self))
-                        .flatten()
+                        .flat_map(|expr| visit_expr(expr, None, self))
                         .collect();
 
-                    Nullable::new(results).nullable_index(0).unwrap_or(true)
+                    // the rows are the elements of the array, a null array has no rows.
+                    match results
+                        .first()
+                        .and_then(|result| result.elements.as_deref())
+                    {
+                        Some(elements) => elements.value.unwrap_or(true),
+                        None => Nullable::new(results).nullable_index(0).unwrap_or(true),
+                    }
                 };
 
                 if let Some(table_alias) = alias {
@@ -102,9 +173,10 @@ impl Context {
                 if let Some(alias) = alias {
                     return self
                         .tables
-                        .0
-                        .iter()
-                        .find(|t| t.table_name.as_deref() == Some(&[alias.name.clone()]))
+                        .iter_scoped()
+                        .find(|t| {
+                            t.table_name.as_deref() == Some(std::slice::from_ref(&alias.name))
+                        })
                         .cloned();
                 }
                 self.tables.find_table_by_idents_table(&name.0).cloned()
@@ -116,13 +188,13 @@ impl Context {
     pub fn recursive_find_joined_tables(&self, expr: &Expr, tables: &mut HashSet<Table>) {
         match expr {
             Expr::CompoundIdentifier(idents) => {
-                let table = self.tables.find_col_by_idents(&idents).unwrap();
+                let table = self.tables.find_col_by_idents(idents).unwrap();
 
                 tables.insert(table.1.clone());
             }
             Expr::BinaryOp { left, op: _, right } => {
-                self.recursive_find_joined_tables(&left, tables);
-                self.recursive_find_joined_tables(&right, tables);
+                self.recursive_find_joined_tables(left, tables);
+                self.recursive_find_joined_tables(right, tables);
             }
             Expr::Subscript { expr, subscript: _ } => {
                 self.recursive_find_joined_tables(expr, tables)
@@ -132,22 +204,36 @@ impl Context {
         }
     }
 
+    /// Runs `f` in its own scope, the tables and the wal entries it adds are removed afterwards.
+    pub fn scoped<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let wal_len = self.wal.data.len();
+        self.tables.enter_scope();
+
+        let result = f(self);
+
+        self.tables.exit_scope();
+        self.wal.data.truncate(wal_len);
+        result
+    }
+
     pub fn add_with(&mut self, with: &With) -> anyhow::Result<()> {
         for cte in &with.cte_tables {
-            let _ = self.nullable_for(cte)?;
+            if with.recursive {
+                self.add_recursive_cte(cte)?;
+            } else {
+                let _ = self.nullable_for(cte)?;
+            }
         }
         Ok(())
     }
 
+    /// The tables of the query that is visited, without the tables of the outer queries.
     pub fn iter_tables(&self) -> impl Iterator<Item = &Table> {
-        self.tables.0.iter()
+        self.tables.iter_scope()
     }
 
     pub fn find_table_by_idents_table(&self, name: &[Ident]) -> Option<&Table> {
-        self.tables
-            .0
-            .iter()
-            .find(|t| t.table_name.as_deref() == Some(name))
+        self.tables.find_table_by_idents_table(name)
     }
 
     pub fn nullable_for_table_col(
@@ -156,92 +242,72 @@ impl Context {
         col: &TableColumn,
     ) -> anyhow::Result<NullableResult> {
         let col_name = col.column_name.clone();
+        let catalog = col.catalog_result();
+
+        // check col always null in wal
+        if self.wal.always_null_for_col(table, col.column_id) {
+            return Ok(NullableResult::always_null(col_name));
+        }
 
         // check col nullable in wal
         if let Some(wal_nullable) = self.wal.nullable_for_col(table, col.column_id) {
             // println!("found col null {} {col_name:?}", wal_nullable);
-            return Ok(NullableResult::new(Some(wal_nullable), col_name));
+            return Ok(NullableResult {
+                value: Some(wal_nullable),
+                ..catalog
+            });
         }
 
         // check table nullable in wal
         if let Some(wal_nullable) = self.nullable_for_table(table) {
             // println!(
-                // "found table null {} {col_name:?} {:?}",
-                // wal_nullable, table.table_id
+            // "found table null {} {col_name:?} {:?}",
+            // wal_nullable, table.table_id
             // );
             if wal_nullable {
-                return Ok(NullableResult::new(Some(wal_nullable), col_name));
+                return Ok(NullableResult {
+                    value: Some(wal_nullable),
+                    ..catalog
+                });
             }
         }
 
-        Ok(NullableResult::new(Some(col.catalog_nullable), col_name))
-    }
-    pub fn nullable_for_ident(&self, name: &[Ident]) -> anyhow::Result<NullableResult> {
-        let (col, table) = self.find_col_by_idents(name)?;
-        self.nullable_for_table_col(table, &col)
+        Ok(catalog)
     }
-    pub fn find_col_by_idents(&self, name: &[Ident]) -> anyhow::Result<(TableColumn, &Table)> {
-        // search for col
-        if name.len() == 1 {
-            for table in self.tables.0.iter() {
-                for col in &table.columns {
-                    if col.column_name.as_ref() == Some(&name[0]) {
-                        return Ok((col.clone(), table));
-                    }
-                }
-            }
-        }
 
-        // look for original name: `table_alias`.`col_name`
-        if let Some(table) = self
-            .tables
-            .0
-            .iter()
-            .find(|table| table.table_name.as_deref() == Some(&name[..name.len() - 1]))
-        {
-            if let Some(col) = table
-                .columns
-                .iter()
-                .find(|column| column.column_name.as_ref() == name.last())
-            {
-                return Ok((col.clone(), table));
-            }
-        }
-
-        // look for original name: `original_table_name`.`col_name`
-        if let Some(table) = self
-            .tables
-            .0
+    /// The whole row of a table, `SELECT t FROM t`, null when the table is outer joined.
+    pub fn nullable_for_table_row(
+        &self,
+        table: &Table,
+        name: Option<Ident>,
+    ) -> anyhow::Result<NullableResult> {
+        let fields = table
+            .columns
             .iter()
-            .find(|table| table.original_name.as_deref() == Some(&name[..name.len() - 1]))
-        {
-            if let Some(col) = table
-                .columns
-                .iter()
-                .find(|column| column.column_name.as_ref() == name.last())
-            {
-                return Ok((col.clone(), table));
-            }
-        }
+            .map(|col| self.nullable_for_table_col(table, col))
+            .collect::<anyhow::Result<_>>()?;
 
-        return Err(anyhow!("Not found"));
+        Ok(
+            NullableResult::new(Some(self.nullable_for_table(table).unwrap_or(false)), name)
+                .with_fields(Some(fields)),
+        )
     }
 
-    pub fn push(&mut self, mut table: Table) {
-        for cur_table in self.tables.0.iter() {
-            // don't insert duplicate tables
-            if cur_table.equals(&table) {
-                return;
-            }
-        }
-
-        table.table_id = TableId::new(self.tables.len());
-
-        for col in table.columns.iter_mut() {
-            col.table_id = table.table_id
+    pub fn nullable_for_ident(&self, name: &[Ident]) -> anyhow::Result<NullableResult> {
+        match self.find_col_by_idents(name) {
+            Ok((col, table)) => self.nullable_for_table_col(table, &col),
+            Err(err) => match self.find_table_by_idents_table(name) {
+                Some(table) => self.nullable_for_table_row(table, name.last().cloned()),
+                None => Err(err),
+            },
         }
+    }
+    pub fn find_col_by_idents(&self, name: &[Ident]) -> anyhow::Result<(TableColumn, &Table)> {
+        self.tables.find_col_by_idents(name)
+    }
 
-        self.tables.0.push(table)
+    pub fn push(&mut self, table: Table) {
+        self.tables.push(table)
     }
 
     pub fn nullable_for_table(&self, table: &Table) -> Option<bool> {
@@ -256,3 +322,12 @@ impl Context {
         None
     }
 }
+
+/// The table of a view named `name`, `CREATE VIEW name (columns)` renames the columns.
+pub fn table_of_view(name: &[Ident], columns: &[Ident], mut nullable: Nullable) -> Table {
+    for (col, col_name) in nullable.iter_mut().zip(columns) {
+        col.column_name = Some(col_name.clone());
+    }
+
+    nullable.to_schema_table(name)
+}
@@ -0,0 +1,48 @@
+use sqlparser::ast::{ObjectName, Query, SelectInto, SetExpr, ViewColumnDef};
+
+use crate::{context::Context, nullable::StatementNullable, View};
+
+impl Context {
+    /// `CREATE [TEMP] TABLE name AS query` and `SELECT ... INTO [TEMP] name`, the columns of the
+    /// new table take the nullability of the query and the table is added to the source.
+    pub fn add_table_from_query(
+        &mut self,
+        name: &ObjectName,
+        temporary: bool,
+        query: &Query,
+    ) -> anyhow::Result<StatementNullable> {
+        let nullable = self.nullable_for(query)?.flatten();
+        let mut table = nullable.to_schema_table(&name.0);
+        if temporary {
+            table.schema.get_or_insert(self.flavour.temp_schema());
+        }
+        self.source.push(table);
+        Ok(StatementNullable::new())
+    }
+
+    /// `CREATE [MATERIALIZED] VIEW name [(columns)] AS query`, the view is added to the source
+    /// and its query is analysed when it's used.
+    pub fn add_view_from_query(
+        &mut self,
+        name: &ObjectName,
+        columns: &[ViewColumnDef],
+        query: &Query,
+    ) -> anyhow::Result<StatementNullable> {
+        self.source
+            .push_view(View::from_query(name, columns, Box::new(query.clone())));
+        Ok(StatementNullable::new())
+    }
+}
+
+/// The `INTO` of a query, it's part of the first select of the query.
+pub fn select_into(query: &Query) -> Option<&SelectInto> {
+    let mut body = query.body.as_ref();
+    loop {
+        match body {
+            SetExpr::Select(select) => return select.into.as_ref(),
+            SetExpr::SetOperation { left, .. } => body = left,
+            SetExpr::Query(query) => body = &query.body,
+            _ => return None,
+        }
+    }
+}
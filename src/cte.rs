@@ -1,6 +1,13 @@
-use sqlparser::ast::Cte;
+use sqlparser::ast::{Cte, SetExpr, SetOperator};
 
-use crate::{context::Context, nullable::{GetNullable, StatementNullable}};
+use crate::{
+    context::Context,
+    nullable::{GetNullable, Nullable, NullableResult, StatementNullable},
+    Table,
+};
+
+/// The most iterations of a recursive member, nested records can keep changing the columns.
+const MAX_RECURSIVE_ITERATIONS: usize = 32;
 
 impl GetNullable for Cte {
     fn nullable_for(
@@ -9,9 +16,69 @@ impl GetNullable for Cte {
     ) -> anyhow::Result<crate::nullable::StatementNullable> {
         let nullable = context.nullable_for(&cte.query)?.flatten();
 
-        let table = nullable.to_table(vec![cte.alias.name.clone()]);
-
-        context.source.push(table);
+        context.source.push(cte_table(cte, nullable));
         Ok(StatementNullable::new())
     }
 }
+
+impl Context {
+    /// Adds a cte of `WITH RECURSIVE`, the table is seeded from the anchor member and the
+    /// recursive member is visited until the nullability of the columns stops changing.
+    /// When it doesn't settle, the columns are nullable.
+    pub fn add_recursive_cte(&mut self, cte: &Cte) -> anyhow::Result<()> {
+        let SetExpr::SetOperation {
+            op: SetOperator::Union,
+            left: anchor,
+            right: recursive,
+            ..
+        } = cte.query.body.as_ref()
+        else {
+            let _ = self.nullable_for(cte)?;
+            return Ok(());
+        };
+
+        // the members are visited in their own scope, their tables aren't visible outside.
+        let mut nullable = self
+            .scoped(|context| context.nullable_for(anchor))?
+            .flatten();
+
+        let source_len = self.source.len();
+        let param_index = self.source.next_param_index;
+
+        for _ in 0..MAX_RECURSIVE_ITERATIONS {
+            let table = cte_table(cte, nullable.clone());
+            self.source.truncate(source_len);
+            self.source.push(table.clone());
+
+            // every iteration starts from the same params.
+            self.source.next_param_index = param_index;
+
+            let recursive = self.scoped(|context| context.nullable_for(recursive));
+
+            // the names of the columns come from the anchor member.
+            let mut combined = StatementNullable::from(recursive?.flatten());
+            combined.push(nullable);
+            nullable = combined.flatten();
+
+            if cte_table(cte, nullable.clone()) == table {
+                return Ok(());
+            }
+        }
+
+        // without a fixpoint the columns could be anything.
+        for col in nullable.iter_mut() {
+            *col = NullableResult::new(Some(true), col.column_name.clone());
+        }
+        self.source.truncate(source_len);
+        self.source.push(cte_table(cte, nullable));
+        Ok(())
+    }
+}
+
+/// The table of a cte, `WITH t(a, b) AS (...)` renames the columns.
+fn cte_table(cte: &Cte, mut nullable: Nullable) -> Table {
+    for (col, col_name) in nullable.iter_mut().zip(cte.alias.columns.clone()) {
+        col.column_name = Some(col_name);
+    }
+    nullable.to_table(vec![cte.alias.name.clone()])
+}
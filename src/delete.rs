@@ -7,14 +7,12 @@ use crate::{
 
 impl GetNullable for Delete {
     fn nullable_for(context: &mut Context, delete: &Self) -> anyhow::Result<StatementNullable> {
-        match &delete.from {
-            FromTable::WithFromKeyword(tables) => {
-                for table in tables {
-                    context.add_active_tables(table)?;
-                }
-            }
-            FromTable::WithoutKeyword(_) => (),
-        }
+        let (FromTable::WithFromKeyword(tables) | FromTable::WithoutKeyword(tables)) = &delete.from;
+
+        // the tables of `USING` are joined with the deleted table.
+        let mut tables = tables.clone();
+        tables.extend(delete.using.iter().flatten().cloned());
+        context.add_from_where(&tables, delete.selection.as_ref())?;
 
         if let Some(returning) = &delete.returning {
             return context.nullable_for(returning);
@@ -0,0 +1,153 @@
+use sqlparser::ast::{BinaryOperator, Expr, UnaryOperator, Value};
+
+use crate::nullable::NullableResult;
+
+/// A value that an expression evaluates to without looking at any row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constant {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl Constant {
+    /// The three-valued truth of the constant, `Some(None)` is unknown and `None` when the truth
+    /// of the constant depends on the database, like the cast of a string to a boolean.
+    pub fn truth(&self) -> Option<Option<bool>> {
+        match self {
+            Constant::Null => Some(None),
+            Constant::Bool(value) => Some(Some(*value)),
+            Constant::Number(value) => Some(Some(*value != 0.0)),
+            Constant::String(_) => None,
+        }
+    }
+
+    pub fn to_nullable(&self) -> NullableResult {
+        match self {
+            Constant::Null => NullableResult::always_null(None),
+            _ => NullableResult::unnamed(Some(false)),
+        }
+    }
+
+    fn number(&self) -> Option<f64> {
+        match self {
+            Constant::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+            Constant::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Folds `expr` into a constant, returns `None` when the expression depends on a row, a
+/// parameter or on something that isn't understood.
+pub fn fold_constant(expr: &Expr) -> Option<Constant> {
+    match expr {
+        Expr::Value(value) => match value {
+            Value::Null => Some(Constant::Null),
+            Value::Boolean(value) => Some(Constant::Bool(*value)),
+            Value::Number(value, _) => value.parse().ok().map(Constant::Number),
+            Value::SingleQuotedString(value) => Some(Constant::String(value.clone())),
+            _ => None,
+        },
+        Expr::Nested(nested) => fold_constant(nested),
+        Expr::UnaryOp { op, expr } => {
+            let value = fold_constant(expr)?;
+            match (op, value) {
+                (_, Constant::Null) => Some(Constant::Null),
+                (UnaryOperator::Not, value) => match value.truth()? {
+                    Some(value) => Some(Constant::Bool(!value)),
+                    None => Some(Constant::Null),
+                },
+                (UnaryOperator::Minus, value) => {
+                    value.number().map(|value| Constant::Number(-value))
+                }
+                (UnaryOperator::Plus, value) => value.number().map(Constant::Number),
+                _ => None,
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let left = fold_truth(left);
+            let right = fold_truth(right);
+
+            match (left, right) {
+                (Some(Some(false)), _) | (_, Some(Some(false))) => Some(Constant::Bool(false)),
+                (Some(Some(true)), Some(Some(true))) => Some(Constant::Bool(true)),
+                (Some(_), Some(_)) => Some(Constant::Null),
+                _ => None,
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Or,
+            right,
+        } => {
+            let left = fold_truth(left);
+            let right = fold_truth(right);
+
+            match (left, right) {
+                (Some(Some(true)), _) | (_, Some(Some(true))) => Some(Constant::Bool(true)),
+                (Some(Some(false)), Some(Some(false))) => Some(Constant::Bool(false)),
+                (Some(_), Some(_)) => Some(Constant::Null),
+                _ => None,
+            }
+        }
+        Expr::BinaryOp { left, op, right } => {
+            let left = fold_constant(left)?;
+            let right = fold_constant(right)?;
+
+            if left == Constant::Null || right == Constant::Null {
+                return Some(Constant::Null);
+            }
+
+            fold_binary_op(&left, op, &right)
+        }
+        Expr::IsNull(expr) => Some(Constant::Bool(fold_constant(expr)? == Constant::Null)),
+        Expr::IsNotNull(expr) => Some(Constant::Bool(fold_constant(expr)? != Constant::Null)),
+        Expr::IsTrue(expr) => fold_truth(expr).map(|truth| Constant::Bool(truth == Some(true))),
+        Expr::IsFalse(expr) => fold_truth(expr).map(|truth| Constant::Bool(truth == Some(false))),
+        Expr::IsNotTrue(expr) => fold_truth(expr).map(|truth| Constant::Bool(truth != Some(true))),
+        Expr::IsNotFalse(expr) => {
+            fold_truth(expr).map(|truth| Constant::Bool(truth != Some(false)))
+        }
+        _ => None,
+    }
+}
+
+/// Folds `expr` into its three-valued truth.
+pub fn fold_truth(expr: &Expr) -> Option<Option<bool>> {
+    fold_constant(expr)?.truth()
+}
+
+fn fold_binary_op(left: &Constant, op: &BinaryOperator, right: &Constant) -> Option<Constant> {
+    if let (Some(left), Some(right)) = (left.number(), right.number()) {
+        return match op {
+            BinaryOperator::Plus => Some(Constant::Number(left + right)),
+            BinaryOperator::Minus => Some(Constant::Number(left - right)),
+            BinaryOperator::Multiply => Some(Constant::Number(left * right)),
+            BinaryOperator::Eq => Some(Constant::Bool(left == right)),
+            BinaryOperator::NotEq => Some(Constant::Bool(left != right)),
+            BinaryOperator::Lt => Some(Constant::Bool(left < right)),
+            BinaryOperator::LtEq => Some(Constant::Bool(left <= right)),
+            BinaryOperator::Gt => Some(Constant::Bool(left > right)),
+            BinaryOperator::GtEq => Some(Constant::Bool(left >= right)),
+            _ => None,
+        };
+    }
+
+    if let (Constant::String(left), Constant::String(right)) = (left, right) {
+        return match op {
+            BinaryOperator::StringConcat => Some(Constant::String(format!("{left}{right}"))),
+            BinaryOperator::Eq => Some(Constant::Bool(left == right)),
+            BinaryOperator::NotEq => Some(Constant::Bool(left != right)),
+            // the order of strings depends on the collation.
+            _ => None,
+        };
+    }
+
+    None
+}
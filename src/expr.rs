@@ -1,11 +1,13 @@
 use anyhow::{anyhow, Context as _};
-use sqlparser::ast::{BinaryOperator, CastKind, Expr, Ident, Value};
+use sqlparser::ast::{BinaryOperator, CastKind, DataType, Expr, Ident, Subscript, Value};
 
 use crate::{
     context::Context,
+    eval::fold_constant,
     func::visit_func,
     join_resolver::JoinResolver,
     nullable::{Nullable, NullableResult},
+    predicate::{conjuncts, non_null_columns, null_columns},
     TableColumn,
 };
 
@@ -16,12 +18,12 @@ pub fn visit_expr(
 ) -> anyhow::Result<NullableResult> {
     match expr {
         Expr::CompoundIdentifier(idents) => {
-            let value = context.nullable_for_ident(&idents)?.set_alias(alias);
+            let value = context.nullable_for_ident(idents)?.set_alias(alias);
             Ok(value)
         }
         Expr::Identifier(col_name) => {
             let value = context
-                .nullable_for_ident(&[col_name.clone()])?
+                .nullable_for_ident(std::slice::from_ref(col_name))?
                 .set_alias(alias);
             Ok(value)
         }
@@ -34,36 +36,175 @@ pub fn visit_expr(
             negated: _,
         } => Ok(NullableResult::unnamed(Some(false))),
         Expr::Value(value) => match value {
-            Value::Null => Ok(NullableResult::unnamed(Some(true)).set_alias(alias)),
+            Value::Null => Ok(NullableResult::always_null(None).set_alias(alias)),
             Value::Placeholder(param) => {
-                Ok(NullableResult::unnamed(context.nullable_for_param(&param)?).set_alias(alias))
+                Ok(NullableResult::unnamed(context.nullable_for_param(param)?).set_alias(alias))
             }
             _ => Ok(NullableResult::unnamed(Some(false)).set_alias(alias)),
         },
         Expr::Cast {
-            kind: CastKind::DoubleColon,
+            kind: CastKind::TryCast | CastKind::SafeCast,
             expr,
-            data_type: _,
+            ..
+        } => {
+            // `TRY_CAST` and `SAFE_CAST` return null when the cast fails.
+            visit_expr(expr, None, context)?;
+            Ok(NullableResult::unnamed(Some(true)).set_alias(alias))
+        }
+        Expr::Cast {
+            kind: CastKind::Cast | CastKind::DoubleColon,
+            expr,
+            data_type,
             format: _,
-        } => visit_expr(expr, alias, context),
-        Expr::Tuple(_tuple) => Ok(NullableResult::unnamed(Some(false)).set_alias(alias)),
-        Expr::Nested(nested) => visit_expr(&nested, alias, context),
+        } => {
+            let nullable = visit_expr(expr, alias, context)?;
+
+            // casting null to a `NOT NULL` domain fails.
+            match data_type {
+                DataType::Custom(name, _)
+                    if context
+                        .source
+                        .find_domain(&name.0)
+                        .is_some_and(|domain| domain.not_null) =>
+                {
+                    Ok(NullableResult::new(Some(false), nullable.column_name))
+                }
+                _ => Ok(nullable),
+            }
+        }
+        Expr::Convert { expr, .. } | Expr::Collate { expr, .. } => visit_expr(expr, alias, context),
+        Expr::TypedString { .. } | Expr::IntroducedString { .. } => {
+            Ok(NullableResult::unnamed(Some(false)).set_alias(alias))
+        }
+        Expr::Interval(interval) => visit_strict(&[&interval.value], alias, context),
+        Expr::UnaryOp { op: _, expr: inner } => {
+            let nullable = visit_expr(inner, None, context)?;
+
+            if let Some(constant) = fold_constant(expr) {
+                return Ok(constant.to_nullable().set_alias(alias));
+            }
+            Ok(NullableResult::unnamed(nullable.value).set_alias(alias))
+        }
+        Expr::Extract { expr, .. } | Expr::Ceil { expr, .. } | Expr::Floor { expr, .. } => {
+            visit_strict(&[expr], alias, context)
+        }
+        Expr::AtTimeZone {
+            timestamp,
+            time_zone,
+        } => visit_strict(&[timestamp, time_zone], alias, context),
+        Expr::Position { expr, r#in } => visit_strict(&[expr, r#in], alias, context),
+        Expr::Substring {
+            expr,
+            substring_from,
+            substring_for,
+            special: _,
+        } => {
+            let mut exprs = vec![expr.as_ref()];
+            exprs.extend(substring_from.as_deref());
+            exprs.extend(substring_for.as_deref());
+            visit_strict(&exprs, alias, context)
+        }
+        Expr::Trim {
+            expr,
+            trim_where: _,
+            trim_what,
+            trim_characters,
+        } => {
+            let mut exprs: Vec<&Expr> = trim_what.as_deref().into_iter().collect();
+            exprs.push(expr);
+            exprs.extend(trim_characters.iter().flatten());
+            visit_strict(&exprs, alias, context)
+        }
+        Expr::Overlay {
+            expr,
+            overlay_what,
+            overlay_from,
+            overlay_for,
+        } => {
+            let mut exprs = vec![expr.as_ref(), overlay_what, overlay_from];
+            exprs.extend(overlay_for.as_deref());
+            visit_strict(&exprs, alias, context)
+        }
+        Expr::Between {
+            expr,
+            negated: _,
+            low,
+            high,
+        } => visit_strict(&[expr, low, high], alias, context),
+        Expr::Like { expr, pattern, .. }
+        | Expr::ILike { expr, pattern, .. }
+        | Expr::SimilarTo { expr, pattern, .. }
+        | Expr::RLike { expr, pattern, .. } => visit_strict(&[expr, pattern], alias, context),
+        Expr::AnyOp { left, right, .. } | Expr::AllOp { left, right, .. } => {
+            let left = visit_expr(left, None, context)?;
+            let right = visit_expr(right, None, context)?;
+
+            // a null element makes the comparison null when no other element decides it.
+            let mut nullables = vec![left.value, right.value];
+            if let Some(elements) = right.elements {
+                nullables.push(elements.value);
+            }
+            Ok(NullableResult::unnamed(strict_value(&nullables)).set_alias(alias))
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            if let Some(operand) = operand {
+                visit_expr(operand, None, context)?;
+            }
+
+            let mut nullable = Nullable::empty();
+            for (condition, result) in conditions.iter().zip(results) {
+                visit_expr(condition, None, context)?;
+                nullable.push(visit_expr(result, None, context)?);
+            }
+
+            // without an `ELSE` the result is null when nothing matches.
+            match else_result {
+                Some(else_result) => nullable.push(visit_expr(else_result, None, context)?),
+                None => nullable.push(NullableResult::unnamed(Some(true))),
+            }
+
+            Ok(nullable
+                .to_result()
+                .ok_or(anyhow!("Geen output gevonden"))?
+                .set_alias(alias))
+        }
+        Expr::Tuple(tuple) => {
+            let mut fields = Vec::new();
+            for expr in tuple {
+                fields.push(visit_expr(expr, None, context)?);
+            }
+            Ok(NullableResult::unnamed(Some(false))
+                .with_fields(Some(record_fields(fields)))
+                .set_alias(alias))
+        }
+        Expr::Nested(nested) => visit_expr(nested, alias, context),
+        // `->`, `->>`, `#>` and `#>>` return null when the path doesn't exist.
+        Expr::BinaryOp { left, op, right } if is_json_path_operator(op) => {
+            visit_strict(&[left, right], None, context)?;
+            Ok(NullableResult::unnamed(Some(true)).set_alias(alias))
+        }
+        Expr::JsonAccess { value, path: _ } => {
+            visit_expr(value, None, context)?;
+            Ok(NullableResult::unnamed(Some(true)).set_alias(alias))
+        }
         Expr::BinaryOp { left, op: _, right } => {
-            let left_nullable = visit_expr(&left, alias.clone(), context)?;
-            let right_nullable = visit_expr(&right, alias, context)?;
+            let nullable = visit_strict(&[left, right], alias.clone(), context)?;
 
-            if left_nullable.value == Some(false) && right_nullable.value == Some(false) {
-                return Ok(NullableResult::unnamed(Some(false)));
-            } else if left_nullable.value == Some(true) || right_nullable.value == Some(true) {
-                return Ok(NullableResult::unnamed(Some(true)));
-            } else {
-                return Ok(NullableResult::unnamed(None));
+            // `x AND false`, `x OR true` and `1 + 2` don't depend on the row.
+            if let Some(constant) = fold_constant(expr) {
+                return Ok(constant.to_nullable().set_alias(alias));
             }
+            Ok(nullable)
         }
         Expr::Subquery(query) => {
             let r = context
                 .nullable_for(query)
-                .map(|r| r.get_nullable().iter().any(|n| *n == Some(true)))?;
+                .map(|r| r.get_nullable().contains(&Some(true)))?;
             Ok(NullableResult::unnamed(Some(r)).set_alias(alias))
         }
         Expr::Array(array) => {
@@ -71,9 +212,56 @@ pub fn visit_expr(
             for expr in &array.elem {
                 nullable.push(visit_expr(expr, None, context)?);
             }
-            nullable.to_result().ok_or(anyhow!("Geen output gevonden"))
+            let elements = nullable
+                .to_result()
+                .map(|elements| NullableResult {
+                    column_name: None,
+                    ..elements
+                })
+                .unwrap_or(NullableResult::unnamed(Some(false)));
+
+            Ok(NullableResult::unnamed(Some(false))
+                .with_elements(Some(elements))
+                .set_alias(alias))
+        }
+        Expr::Subscript { expr, subscript } => {
+            let nullable = visit_expr(expr, None, context)?;
+            let bounds = visit_subscript(subscript, context)?;
+
+            match subscript.as_ref() {
+                // out of range subscripts are null.
+                Subscript::Index { .. } => {
+                    Ok(NullableResult::new(Some(true), nullable.column_name).set_alias(alias))
+                }
+                // out of range slices are empty arrays.
+                Subscript::Slice { .. } => {
+                    let mut nullables = vec![nullable.value];
+                    nullables.extend(bounds.iter().map(|bound| bound.value));
+                    Ok(NullableResult {
+                        value: strict_value(&nullables),
+                        ..nullable
+                    }
+                    .set_alias(alias))
+                }
+            }
+        }
+        // missing keys are null.
+        Expr::MapAccess { column, keys } => {
+            let nullable = visit_expr(column, None, context)?;
+            for key in keys {
+                visit_expr(&key.key, None, context)?;
+            }
+            Ok(NullableResult::new(Some(true), nullable.column_name).set_alias(alias))
+        }
+        Expr::CompositeAccess { expr, key } => {
+            let nullable = visit_expr(expr, None, context)?;
+            let field = nullable.field(key).unwrap_or(NullableResult {
+                elements: None,
+                fields: None,
+                ..nullable
+            });
+            Ok(field.set_alias(Some(key.clone())).set_alias(alias))
         }
-        Expr::CompositeAccess { expr, key } => visit_expr(expr, Some(key.clone()), context),
         Expr::InList {
             expr,
             list,
@@ -122,74 +310,121 @@ pub fn visit_expr(
     }
 }
 
+fn is_json_path_operator(op: &BinaryOperator) -> bool {
+    matches!(
+        op,
+        BinaryOperator::Arrow
+            | BinaryOperator::LongArrow
+            | BinaryOperator::HashArrow
+            | BinaryOperator::HashLongArrow
+    )
+}
+
+/// Visits the operands of an expression that is null when any of its operands is null.
+fn visit_strict(
+    exprs: &[&Expr],
+    alias: Option<Ident>,
+    context: &mut Context,
+) -> anyhow::Result<NullableResult> {
+    let mut nullables = Vec::new();
+    for expr in exprs {
+        nullables.push(visit_expr(expr, None, context)?.value);
+    }
+
+    Ok(NullableResult::unnamed(strict_value(&nullables)).set_alias(alias))
+}
+
+/// Visits the index or the bounds of a subscript.
+fn visit_subscript(
+    subscript: &Subscript,
+    context: &mut Context,
+) -> anyhow::Result<Vec<NullableResult>> {
+    match subscript {
+        Subscript::Index { index } => Ok(vec![visit_expr(index, None, context)?]),
+        Subscript::Slice {
+            lower_bound,
+            upper_bound,
+            stride,
+        } => {
+            let mut bounds = Vec::new();
+            for bound in [lower_bound, upper_bound, stride].into_iter().flatten() {
+                bounds.push(visit_expr(bound, None, context)?);
+            }
+            Ok(bounds)
+        }
+    }
+}
+
+/// The fields of an anonymous record are named `f1`, `f2`, ...
+pub fn record_fields(fields: Vec<NullableResult>) -> Vec<NullableResult> {
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(index, field)| field.set_alias(Some(Ident::new(format!("f{}", index + 1)))))
+        .collect()
+}
+
+fn strict_value(nullables: &[Option<bool>]) -> Option<bool> {
+    if nullables.iter().all(|n| *n == Some(false)) {
+        Some(false)
+    } else if nullables.contains(&Some(true)) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
 pub fn get_nullable_col(
     expr: &Expr,
     context: &mut Context,
     join_resolvers: &mut [JoinResolver],
 ) -> anyhow::Result<()> {
-    match expr {
-        Expr::IsNotNull(not_null) => {
-            if let Some(column) = get_column(&not_null, context)? {
-                context
-                    .wal
-                    .add_column(column.table_id, column.column_id, false);
-                for t in join_resolvers {
-                    t.set_nullable(column.table_id, Some(false));
-                }
+    for conjunct in conjuncts(expr) {
+        for column in non_null_columns(conjunct, true, context)? {
+            context
+                .wal
+                .add_column(column.table_id, column.column_id, false);
+            for t in &mut *join_resolvers {
+                t.set_nullable(column.table_id, Some(false));
             }
-            Ok(())
         }
-        Expr::BinaryOp { left, op, right } => {
-            if let (Some(left_col), Some(false)) = (
-                get_column(&left, context)?,
-                visit_expr(&right, None, context)?.value,
-            ) {
-                context
-                    .wal
-                    .add_column(left_col.table_id, left_col.column_id, false);
-                for t in &mut *join_resolvers {
-                    t.set_nullable(left_col.table_id, Some(false));
-                }
-            }
 
-            if let (Some(right_col), Some(false)) = (
-                get_column(&right, context)?,
-                visit_expr(&left, None, context)?.value,
-            ) {
-                context
-                    .wal
-                    .add_column(right_col.table_id, right_col.column_id, false);
-                for t in &mut *join_resolvers {
-                    t.set_nullable(right_col.table_id, Some(false));
-                }
-            }
+        for column in null_columns(conjunct, true, context)? {
+            // a non null column can only be null when the row of an outer joined table is missing.
+            let table = context
+                .tables
+                .find_table_id(column.table_id)
+                .filter(|_| !column.catalog_nullable)
+                .cloned();
 
-            if *op != BinaryOperator::And {
-                return Ok(());
+            match table {
+                Some(table) => {
+                    for column in table.columns {
+                        context
+                            .wal
+                            .add_null_column(column.table_id, column.column_id);
+                    }
+                }
+                None => context
+                    .wal
+                    .add_null_column(column.table_id, column.column_id),
             }
-            get_nullable_col(left, context, join_resolvers)?;
-            get_nullable_col(right, context, join_resolvers)?;
-
-            return Ok(());
         }
-        Expr::CompoundIdentifier(_) => Ok(()),
-        Expr::Identifier(_ident) => Ok(()),
-        Expr::Value(_) => Ok(()),
-        _ => unimplemented!("{expr:?}"),
     }
+    Ok(())
 }
 
-fn get_column(expr: &Expr, context: &mut Context) -> anyhow::Result<Option<TableColumn>> {
+pub fn get_column(expr: &Expr, context: &mut Context) -> anyhow::Result<Option<TableColumn>> {
     match expr {
         Expr::CompoundIdentifier(idents) => {
             let (col, _table) = context
-                .find_col_by_idents(&idents)
+                .find_col_by_idents(idents)
                 .context(format!("table not found: {expr:?}"))?;
             Ok(Some(col))
         }
         Expr::Identifier(ident) => {
             let (col, _table) = context
-                .find_col_by_idents(&[ident.clone()])
+                .find_col_by_idents(std::slice::from_ref(ident))
                 .context(format!("table not found: {expr:?}"))?;
             Ok(Some(col))
         }
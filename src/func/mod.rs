@@ -2,81 +2,179 @@ use sqlparser::ast::{
     Function, FunctionArg, FunctionArgExpr, FunctionArgumentList, FunctionArguments, ObjectName,
 };
 
-use crate::{context::Context, expr::visit_expr, nullable::NullableResult};
+use crate::{
+    context::Context,
+    expr::{record_fields, visit_expr},
+    nullable::NullableResult,
+    SqlFlavour,
+};
+
+mod postgres;
+mod sqlite;
+
+/// How the nullability of a function follows from its arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuncRule {
+    /// Never returns null.
+    NonNull,
+    /// Can return null for non null arguments.
+    Nullable,
+    /// Returns null when any of the arguments is null.
+    Strict,
+    /// Returns null when all of the arguments are null.
+    AnyArg,
+    /// Returns null when the first argument is null, other null arguments are ignored.
+    FirstArg,
+    /// The first argument decides which one of the other arguments is returned.
+    SkipFirst,
+    /// Aggregate that is null when its input is null or when it aggregates zero rows.
+    Aggregate,
+    /// Aggregate that collects null input, only null when it aggregates zero rows.
+    Collect,
+    /// Aggregate with a single argument, strict scalar function with more arguments.
+    AggregateOrStrict,
+}
+
+impl FuncRule {
+    fn infer(self, nullables: &[Option<bool>], context: &Context) -> Option<bool> {
+        match self {
+            FuncRule::NonNull => Some(false),
+            FuncRule::Nullable => Some(true),
+            FuncRule::Strict => all_non_null(nullables),
+            FuncRule::AnyArg => any_non_null(nullables),
+            FuncRule::FirstArg => nullables.get(..1).and_then(all_non_null),
+            FuncRule::SkipFirst => nullables.get(1..).and_then(all_non_null),
+            // aggregates over zero rows return null.
+            FuncRule::Aggregate | FuncRule::Collect if context.empty_selection => Some(true),
+            FuncRule::Aggregate => all_non_null(nullables),
+            FuncRule::Collect if nullables.is_empty() => None,
+            FuncRule::Collect => Some(false),
+            FuncRule::AggregateOrStrict if nullables.len() > 1 => all_non_null(nullables),
+            FuncRule::AggregateOrStrict => FuncRule::Aggregate.infer(nullables, context),
+        }
+    }
+}
 
 pub fn visit_func(func: &Function, context: &mut Context) -> anyhow::Result<NullableResult> {
     let function_name = func_name(&func.name);
-    let f: Vec<_> = function_name.iter().map(|n| n.as_str()).collect();
-    let inferred_nullable = match f[..] {
-        ["count"] | ["current_user"] | ["now"] | ["random"] | ["version"] => Some(false),
-        ["lower"]
-        | ["upper"]
-        | ["concat"]
-        | ["length"]
-        | ["abs"]
-        | ["ceil"]
-        | ["ceiling"]
-        | ["floor"]
-        | ["round"]
-        | ["power"]
-        | ["sum"]
-        | ["avg"]
-        | ["min"]
-        | ["max"]
-        | ["information_schema", "_pg_expandarray"] => {
-            let nullables = args_nullables(&func.args, context)?;
-
-            if nullables.len() > 0 && nullables.iter().all(|n| *n == Some(false)) {
-                Some(false)
-            } else {
-                None
-            }
-        }
-        ["coalesce"] => {
-            let nullables = args_nullables(&func.args, context)?;
+    let args = args_nullables(&func.args, context)?;
+    let nullables: Vec<_> = args.iter().map(|arg| arg.value).collect();
 
-            if !nullables.is_empty() && nullables.iter().any(|n| *n == Some(false)) {
-                Some(false)
-            } else {
-                None
-            }
-        }
-        ["array_agg"] | ["array_remove"] => {
-            let nullables = args_nullables(&func.args, context)?;
+    let catalogues: &[&[(&str, FuncRule)]] = match context.flavour {
+        SqlFlavour::Postgres => &[postgres::FUNCTIONS, postgres::EXTENSIONS],
+        SqlFlavour::Sqlite => &[sqlite::FUNCTIONS],
+    };
 
-            if !nullables.is_empty() {
-                Some(false)
-            } else {
-                None
-            }
+    // unknown functions are nullable.
+    let inferred_nullable = catalogues
+        .iter()
+        .find_map(|catalogue| find_rule(catalogue, &function_name))
+        .and_then(|rule| rule.infer(&nullables, context));
+
+    let name = catalogue_name(&function_name);
+
+    // set returning functions over an array return its elements.
+    if matches!(
+        name.as_str(),
+        "unnest" | "information_schema._pg_expandarray"
+    ) {
+        if let Some(elements) = args.first().and_then(|arg| arg.elements.as_deref()) {
+            return Ok(NullableResult::unnamed(elements.value));
         }
-        ["current_timestamp"] if args_nullables(&func.args, context)?.is_empty() => Some(false),
-        ["generate_series"] => Some(false),
-        _ => unimplemented!("{func:?}"),
+    }
+
+    // `ROW(a, b)` is a record with the arguments as fields.
+    let fields = (name == "row").then(|| record_fields(args.clone()));
+
+    Ok(NullableResult::unnamed(inferred_nullable)
+        .with_elements(array_elements(&name, &args))
+        .with_fields(fields))
+}
+
+/// Nullability of the elements of the arrays returned by the array functions.
+fn array_elements(name: &str, args: &[NullableResult]) -> Option<NullableResult> {
+    let elements = |index: usize| args.get(index)?.elements.as_deref().cloned();
+    let value = |index: usize| {
+        args.get(index)
+            .map(|arg| NullableResult::unnamed(arg.value))
     };
 
-    Ok(NullableResult::unnamed(inferred_nullable))
+    match name {
+        "array" | "array_agg" => value(0),
+        "array_append" => combine(elements(0), value(1)),
+        "array_prepend" => combine(value(0), elements(1)),
+        "array_cat" => combine(elements(0), elements(1)),
+        "array_replace" => combine(elements(0), value(2)),
+        // `array_remove(arr, NULL)` removes all of the null elements.
+        "array_remove" if args.get(1).is_some_and(|arg| arg.always_null) => {
+            Some(NullableResult::unnamed(Some(false)))
+        }
+        "array_remove" => elements(0),
+        _ => None,
+    }
+}
+
+fn combine(
+    first: Option<NullableResult>,
+    second: Option<NullableResult>,
+) -> Option<NullableResult> {
+    let mut first = first?;
+    first.combine(second?);
+    Some(first)
+}
+
+/// Finds the rule for a function.
+fn find_rule(catalogue: &[(&str, FuncRule)], function_name: &[String]) -> Option<FuncRule> {
+    let name = catalogue_name(function_name);
+
+    catalogue
+        .iter()
+        .find(|(function, _)| *function == name)
+        .map(|(_, rule)| *rule)
+}
+
+/// Non null when all of the arguments are non null.
+fn all_non_null(nullables: &[Option<bool>]) -> Option<bool> {
+    if !nullables.is_empty() && nullables.iter().all(|n| *n == Some(false)) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Non null when one of the arguments is non null.
+fn any_non_null(nullables: &[Option<bool>]) -> Option<bool> {
+    if !nullables.is_empty() && nullables.contains(&Some(false)) {
+        Some(false)
+    } else {
+        None
+    }
 }
 
 fn args_nullables(
     args: &FunctionArguments,
     context: &mut Context,
-) -> anyhow::Result<Vec<Option<bool>>> {
+) -> anyhow::Result<Vec<NullableResult>> {
     match args {
-        FunctionArguments::List(list) => arg_list_nullable(&list, context),
+        FunctionArguments::List(list) => arg_list_nullable(list, context),
         FunctionArguments::None => Ok(Vec::new()),
-        subquery => unimplemented!("{subquery:?}"),
+        FunctionArguments::Subquery(query) => context.nullable_for(query).map(|n| {
+            n.get_nullable()
+                .into_iter()
+                .map(NullableResult::unnamed)
+                .collect()
+        }),
     }
 }
 
 fn arg_list_nullable(
     arg_list: &FunctionArgumentList,
     context: &mut Context,
-) -> anyhow::Result<Vec<Option<bool>>> {
+) -> anyhow::Result<Vec<NullableResult>> {
     arg_list
         .args
         .iter()
-        .map(|a| func_list_arg_nullable(a, context).map(|r| r.value))
+        .map(|a| func_list_arg_nullable(a, context))
         .collect()
 }
 
@@ -85,8 +183,21 @@ fn func_list_arg_nullable(
     context: &mut Context,
 ) -> anyhow::Result<NullableResult> {
     match arg {
-        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => visit_expr(expr, None, context),
-        _ => unimplemented!(),
+        FunctionArg::Unnamed(arg) | FunctionArg::Named { arg, .. } => match arg {
+            FunctionArgExpr::Expr(expr) => visit_expr(expr, None, context),
+            // `count(*)`
+            FunctionArgExpr::Wildcard | FunctionArgExpr::QualifiedWildcard(_) => {
+                Ok(NullableResult::unnamed(Some(false)))
+            }
+        },
+    }
+}
+
+/// Functions in `pg_catalog` can be called with or without their schema.
+fn catalogue_name(function_name: &[String]) -> String {
+    match function_name {
+        [schema, name] if schema == "pg_catalog" => name.clone(),
+        name => name.join("."),
     }
 }
 
@@ -0,0 +1,283 @@
+use super::FuncRule::{self, *};
+
+/// Nullability of the Postgres builtin functions.
+pub static FUNCTIONS: &[(&str, FuncRule)] = &[
+    // null handling
+    ("coalesce", AnyArg),
+    ("nullif", Nullable),
+    ("greatest", AnyArg),
+    ("least", AnyArg),
+    // composite
+    ("row", NonNull),
+    // string
+    ("ascii", Strict),
+    ("bit_length", Strict),
+    ("btrim", Strict),
+    ("char_length", Strict),
+    ("character_length", Strict),
+    ("chr", Strict),
+    ("concat", NonNull),
+    ("concat_ws", FirstArg),
+    ("decode", Strict),
+    ("encode", Strict),
+    ("format", FirstArg),
+    ("initcap", Strict),
+    ("left", Strict),
+    ("length", Strict),
+    ("lower", Strict),
+    ("lpad", Strict),
+    ("ltrim", Strict),
+    ("md5", Strict),
+    ("octet_length", Strict),
+    ("overlay", Strict),
+    ("position", Strict),
+    ("quote_ident", Strict),
+    ("quote_literal", Strict),
+    ("quote_nullable", NonNull),
+    ("regexp_count", Strict),
+    ("regexp_instr", Strict),
+    ("regexp_like", Strict),
+    ("regexp_match", Nullable),
+    ("regexp_matches", Strict),
+    ("regexp_replace", Strict),
+    ("regexp_split_to_array", Strict),
+    ("regexp_split_to_table", Strict),
+    ("regexp_substr", Nullable),
+    ("repeat", Strict),
+    ("replace", Strict),
+    ("reverse", Strict),
+    ("right", Strict),
+    ("rpad", Strict),
+    ("rtrim", Strict),
+    ("sha224", Strict),
+    ("sha256", Strict),
+    ("sha384", Strict),
+    ("sha512", Strict),
+    ("split_part", Strict),
+    ("starts_with", Strict),
+    ("string_to_array", FirstArg),
+    ("strpos", Strict),
+    ("substr", Strict),
+    ("substring", Strict),
+    ("to_hex", Strict),
+    ("translate", Strict),
+    ("trim", Strict),
+    ("upper", Strict),
+    // formatting
+    ("to_char", Strict),
+    ("to_date", Strict),
+    ("to_number", Strict),
+    ("to_timestamp", Strict),
+    // date/time
+    ("age", Strict),
+    ("clock_timestamp", NonNull),
+    ("current_date", NonNull),
+    ("current_time", NonNull),
+    ("current_timestamp", NonNull),
+    ("date_add", Strict),
+    ("date_bin", Strict),
+    ("date_part", Strict),
+    ("date_subtract", Strict),
+    ("date_trunc", Strict),
+    ("extract", Strict),
+    ("isfinite", Strict),
+    ("justify_days", Strict),
+    ("justify_hours", Strict),
+    ("justify_interval", Strict),
+    ("localtime", NonNull),
+    ("localtimestamp", NonNull),
+    ("make_date", Strict),
+    ("make_interval", Strict),
+    ("make_time", Strict),
+    ("make_timestamp", Strict),
+    ("make_timestamptz", Strict),
+    ("now", NonNull),
+    ("statement_timestamp", NonNull),
+    ("timeofday", NonNull),
+    ("transaction_timestamp", NonNull),
+    // math
+    ("abs", Strict),
+    ("acos", Strict),
+    ("asin", Strict),
+    ("atan", Strict),
+    ("atan2", Strict),
+    ("cbrt", Strict),
+    ("ceil", Strict),
+    ("ceiling", Strict),
+    ("cos", Strict),
+    ("cot", Strict),
+    ("degrees", Strict),
+    ("div", Strict),
+    ("exp", Strict),
+    ("factorial", Strict),
+    ("floor", Strict),
+    ("gcd", Strict),
+    ("lcm", Strict),
+    ("ln", Strict),
+    ("log", Strict),
+    ("log10", Strict),
+    ("mod", Strict),
+    ("pi", NonNull),
+    ("pow", Strict),
+    ("power", Strict),
+    ("radians", Strict),
+    ("random", NonNull),
+    ("round", Strict),
+    ("scale", Strict),
+    ("sign", Strict),
+    ("sin", Strict),
+    ("sqrt", Strict),
+    ("tan", Strict),
+    ("trunc", Strict),
+    ("width_bucket", Strict),
+    // json
+    ("array_to_json", Strict),
+    ("json_array_elements", Strict),
+    ("json_array_elements_text", Strict),
+    ("json_array_length", Strict),
+    ("json_build_array", NonNull),
+    ("json_build_object", NonNull),
+    ("json_each", Strict),
+    ("json_each_text", Strict),
+    ("json_extract_path", Nullable),
+    ("json_extract_path_text", Nullable),
+    ("json_object", Strict),
+    ("json_object_keys", Strict),
+    ("json_strip_nulls", Strict),
+    ("json_typeof", Strict),
+    ("jsonb_array_elements", Strict),
+    ("jsonb_array_elements_text", Strict),
+    ("jsonb_array_length", Strict),
+    ("jsonb_build_array", NonNull),
+    ("jsonb_build_object", NonNull),
+    ("jsonb_each", Strict),
+    ("jsonb_each_text", Strict),
+    ("jsonb_extract_path", Nullable),
+    ("jsonb_extract_path_text", Nullable),
+    ("jsonb_insert", Strict),
+    ("jsonb_object", Strict),
+    ("jsonb_object_keys", Strict),
+    ("jsonb_path_exists", Strict),
+    ("jsonb_path_match", Nullable),
+    ("jsonb_path_query", Strict),
+    ("jsonb_path_query_array", Strict),
+    ("jsonb_path_query_first", Nullable),
+    ("jsonb_pretty", Strict),
+    ("jsonb_set", Strict),
+    ("jsonb_strip_nulls", Strict),
+    ("jsonb_typeof", Strict),
+    ("row_to_json", Strict),
+    ("to_json", Strict),
+    ("to_jsonb", Strict),
+    // array
+    ("array", NonNull),
+    ("array_append", NonNull),
+    ("array_cat", AnyArg),
+    ("array_dims", Nullable),
+    ("array_fill", Strict),
+    ("array_length", Nullable),
+    ("array_lower", Nullable),
+    ("array_ndims", Nullable),
+    ("array_position", Nullable),
+    ("array_positions", FirstArg),
+    ("array_prepend", NonNull),
+    ("array_remove", FirstArg),
+    ("array_replace", FirstArg),
+    ("array_to_string", Strict),
+    ("array_upper", Nullable),
+    ("cardinality", Strict),
+    ("generate_series", NonNull),
+    ("generate_subscripts", Strict),
+    ("unnest", Strict),
+    ("information_schema._pg_expandarray", Strict),
+    // range
+    ("daterange", NonNull),
+    ("int4range", NonNull),
+    ("int8range", NonNull),
+    ("isempty", Strict),
+    ("lower_inc", Strict),
+    ("lower_inf", Strict),
+    ("numrange", NonNull),
+    ("range_merge", Strict),
+    ("tsrange", NonNull),
+    ("tstzrange", NonNull),
+    ("upper_inc", Strict),
+    ("upper_inf", Strict),
+    // uuid
+    ("gen_random_uuid", NonNull),
+    ("uuid_generate_v1", NonNull),
+    ("uuid_generate_v4", NonNull),
+    // system
+    ("current_catalog", NonNull),
+    ("current_database", NonNull),
+    ("current_role", NonNull),
+    ("current_schema", NonNull),
+    ("current_user", NonNull),
+    ("currval", NonNull),
+    ("col_description", Nullable),
+    ("format_type", Nullable),
+    ("has_table_privilege", Strict),
+    ("inet_client_addr", Nullable),
+    ("lastval", NonNull),
+    ("nextval", NonNull),
+    ("obj_description", Nullable),
+    ("pg_backend_pid", NonNull),
+    ("pg_column_size", Strict),
+    ("pg_current_xact_id", NonNull),
+    ("pg_relation_size", Strict),
+    ("pg_size_pretty", Strict),
+    ("pg_total_relation_size", Strict),
+    ("pg_typeof", NonNull),
+    ("session_user", NonNull),
+    ("setval", NonNull),
+    ("txid_current", NonNull),
+    ("user", NonNull),
+    ("version", NonNull),
+    // aggregates
+    ("array_agg", Collect),
+    ("avg", Aggregate),
+    ("bit_and", Aggregate),
+    ("bit_or", Aggregate),
+    ("bool_and", Aggregate),
+    ("bool_or", Aggregate),
+    ("count", NonNull),
+    ("every", Aggregate),
+    ("json_agg", Collect),
+    ("json_object_agg", Collect),
+    ("jsonb_agg", Collect),
+    ("jsonb_object_agg", Collect),
+    ("max", Aggregate),
+    ("min", Aggregate),
+    // the arguments of the ordered-set aggregates are the fractions, not the aggregated values.
+    ("mode", Nullable),
+    ("percentile_cont", Nullable),
+    ("percentile_disc", Nullable),
+    ("stddev", Nullable),
+    ("stddev_pop", Aggregate),
+    ("stddev_samp", Nullable),
+    ("string_agg", Aggregate),
+    ("sum", Aggregate),
+    ("var_pop", Aggregate),
+    ("var_samp", Nullable),
+    ("variance", Nullable),
+    // window
+    ("cume_dist", NonNull),
+    ("dense_rank", NonNull),
+    ("first_value", Strict),
+    ("lag", Nullable),
+    ("last_value", Strict),
+    ("lead", Nullable),
+    ("nth_value", Nullable),
+    ("ntile", NonNull),
+    ("percent_rank", NonNull),
+    ("rank", NonNull),
+    ("row_number", NonNull),
+];
+
+/// Nullability of the functions of common Postgres extensions, these are not builtin and only
+/// looked up when no builtin function has the name.
+pub static EXTENSIONS: &[(&str, FuncRule)] = &[
+    // orafce
+    ("nvl", AnyArg),
+    ("nvl2", SkipFirst),
+];
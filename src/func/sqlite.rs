@@ -0,0 +1,143 @@
+use super::FuncRule::{self, *};
+
+/// Nullability of the Sqlite builtin functions.
+pub static FUNCTIONS: &[(&str, FuncRule)] = &[
+    // null handling
+    ("coalesce", AnyArg),
+    ("ifnull", AnyArg),
+    ("iif", SkipFirst),
+    ("nullif", Nullable),
+    ("greatest", Strict),
+    ("least", Strict),
+    // scalar
+    ("abs", Strict),
+    ("changes", NonNull),
+    ("char", NonNull),
+    ("concat", NonNull),
+    ("concat_ws", FirstArg),
+    ("format", FirstArg),
+    ("glob", Strict),
+    ("hex", NonNull),
+    ("instr", Strict),
+    ("last_insert_rowid", NonNull),
+    ("length", Strict),
+    ("like", Strict),
+    ("likelihood", FirstArg),
+    ("likely", FirstArg),
+    ("lower", Strict),
+    ("ltrim", Strict),
+    ("octet_length", Strict),
+    ("printf", FirstArg),
+    ("quote", NonNull),
+    ("random", NonNull),
+    ("randomblob", NonNull),
+    ("replace", Strict),
+    ("round", Strict),
+    ("rtrim", Strict),
+    ("sign", Strict),
+    ("soundex", NonNull),
+    ("sqlite_source_id", NonNull),
+    ("sqlite_version", NonNull),
+    ("substr", Strict),
+    ("substring", Strict),
+    ("total_changes", NonNull),
+    ("trim", Strict),
+    ("typeof", NonNull),
+    ("unhex", Nullable),
+    ("unicode", Strict),
+    ("unlikely", FirstArg),
+    ("upper", Strict),
+    ("zeroblob", Strict),
+    // json, null when the path doesn't exist
+    ("json", Strict),
+    ("json_array", NonNull),
+    ("json_array_length", Nullable),
+    ("json_each", Strict),
+    ("json_error_position", Strict),
+    ("json_extract", Nullable),
+    ("json_insert", Strict),
+    ("json_object", NonNull),
+    ("json_patch", Strict),
+    ("json_quote", NonNull),
+    ("json_remove", Strict),
+    ("json_replace", Strict),
+    ("json_set", Strict),
+    ("json_tree", Strict),
+    ("json_type", Nullable),
+    ("json_valid", Strict),
+    ("jsonb", Strict),
+    ("jsonb_array", NonNull),
+    ("jsonb_extract", Nullable),
+    ("jsonb_insert", Strict),
+    ("jsonb_object", NonNull),
+    ("jsonb_patch", Strict),
+    ("jsonb_remove", Strict),
+    ("jsonb_replace", Strict),
+    ("jsonb_set", Strict),
+    // date/time, null when the input can't be parsed
+    ("current_date", NonNull),
+    ("current_time", NonNull),
+    ("current_timestamp", NonNull),
+    ("date", Nullable),
+    ("datetime", Nullable),
+    ("julianday", Nullable),
+    ("strftime", Nullable),
+    ("time", Nullable),
+    ("timediff", Nullable),
+    ("unixepoch", Nullable),
+    // math, null when the input is outside of the domain of the function
+    ("acos", Nullable),
+    ("acosh", Nullable),
+    ("asin", Nullable),
+    ("asinh", Strict),
+    ("atan", Strict),
+    ("atan2", Strict),
+    ("atanh", Nullable),
+    ("ceil", Strict),
+    ("ceiling", Strict),
+    ("cos", Strict),
+    ("cosh", Strict),
+    ("degrees", Strict),
+    ("exp", Strict),
+    ("floor", Strict),
+    ("ln", Nullable),
+    ("log", Nullable),
+    ("log10", Nullable),
+    ("log2", Nullable),
+    ("mod", Nullable),
+    ("pi", NonNull),
+    ("pow", Nullable),
+    ("power", Nullable),
+    ("radians", Strict),
+    ("sin", Strict),
+    ("sinh", Strict),
+    ("sqrt", Nullable),
+    ("tan", Strict),
+    ("tanh", Strict),
+    ("trunc", Strict),
+    // aggregates, `max(a, b)` and `min(a, b)` are scalar functions
+    ("avg", Aggregate),
+    ("count", NonNull),
+    ("group_concat", Aggregate),
+    ("json_group_array", Collect),
+    ("json_group_object", Collect),
+    ("jsonb_group_array", Collect),
+    ("jsonb_group_object", Collect),
+    ("max", AggregateOrStrict),
+    ("min", AggregateOrStrict),
+    ("string_agg", Aggregate),
+    ("sum", Aggregate),
+    ("total", NonNull),
+    // window
+    ("cume_dist", NonNull),
+    ("dense_rank", NonNull),
+    ("first_value", Strict),
+    ("lag", Nullable),
+    ("last_value", Strict),
+    ("lead", Nullable),
+    ("nth_value", Nullable),
+    ("ntile", NonNull),
+    ("percent_rank", NonNull),
+    ("rank", NonNull),
+    ("row_number", NonNull),
+];
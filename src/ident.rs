@@ -0,0 +1,68 @@
+use sqlparser::{
+    ast::{Ident, Statement},
+    dialect::Dialect,
+    keywords::Keyword,
+    parser::Parser,
+    tokenizer::{Token, Tokenizer, Word},
+};
+
+use crate::SqlFlavour;
+
+/// Identifiers are compared structurally, so they are folded while parsing: Postgres folds
+/// unquoted identifiers to lower case and keeps the case of quoted identifiers, Sqlite compares
+/// identifiers case-insensitively. The quotes are removed, `"users"` is the same as `users`.
+fn fold_value(value: &str, quoted: bool, flavour: SqlFlavour) -> String {
+    match (flavour, quoted) {
+        (SqlFlavour::Postgres, true) => value.to_string(),
+        (SqlFlavour::Postgres, false) | (SqlFlavour::Sqlite, _) => value.to_ascii_lowercase(),
+    }
+}
+
+/// The spelling of the `DEFAULT` keyword after folding.
+pub const DEFAULT_KEYWORD: &str = "DEFAULT";
+
+/// Names of the catalog are the names of the database, they keep their case like quoted
+/// identifiers.
+pub fn fold_catalog(ident: &Ident, flavour: SqlFlavour) -> Ident {
+    Ident::new(fold_value(&ident.value, true, flavour))
+}
+
+/// A parser of `sql` whose identifiers are folded by the rules of `flavour`.
+pub fn parser<'a>(
+    dialect: &'a dyn Dialect,
+    sql: &str,
+    flavour: SqlFlavour,
+) -> anyhow::Result<Parser<'a>> {
+    let mut tokens = Tokenizer::new(dialect, sql).tokenize_with_location()?;
+    for token in tokens.iter_mut() {
+        let Token::Word(word) = &token.token else {
+            continue;
+        };
+        let quoted = word.quote_style.is_some();
+
+        // `DEFAULT` is parsed as an identifier, it's spelled like `DEFAULT_KEYWORD` so it can be
+        // told apart from a quoted identifier, which keeps its quotes when it's spelled the same.
+        let (value, quote_style) = if !quoted && word.keyword == Keyword::DEFAULT {
+            (DEFAULT_KEYWORD.to_string(), None)
+        } else {
+            let value = fold_value(&word.value, quoted, flavour);
+            let quote_style = word.quote_style.filter(|_| value == DEFAULT_KEYWORD);
+            (value, quote_style)
+        };
+
+        token.token = Token::Word(Word {
+            value,
+            quote_style,
+            keyword: word.keyword,
+        });
+    }
+    Ok(Parser::new(dialect).with_tokens_with_locations(tokens))
+}
+
+pub fn parse_sql(
+    dialect: &dyn Dialect,
+    sql: &str,
+    flavour: SqlFlavour,
+) -> anyhow::Result<Vec<Statement>> {
+    Ok(parser(dialect, sql, flavour)?.parse_statements()?)
+}
@@ -1,22 +1,187 @@
 use anyhow::Context as _;
-use sqlparser::ast::Insert;
+use sqlparser::ast::{DoUpdate, Expr, Insert, OnConflict, OnConflictAction, OnInsert, SetExpr};
 
 use crate::{
     context::Context,
-    nullable::{GetNullable, StatementNullable},
+    expr::visit_expr,
+    ident::DEFAULT_KEYWORD,
+    nullable::{GetNullable, Nullable, NullableResult, StatementNullable},
+    ColumnDefault, Table, TableColumn,
 };
 
 impl GetNullable for Insert {
     fn nullable_for(context: &mut Context, insert: &Self) -> anyhow::Result<StatementNullable> {
+        let mut table = context.source_table(&insert.table_name.0)?;
+
+        // the inserted rows come before `RETURNING`, their params are visited first.
+        let mut row = inserted_row(context, insert, &table)?;
+
+        if let Some(OnInsert::OnConflict(OnConflict {
+            action: OnConflictAction::DoUpdate(do_update),
+            ..
+        })) = &insert.on
+        {
+            let updated =
+                context.scoped(|context| updated_row(context, insert, &table, &row, do_update))?;
+
+            // `RETURNING` returns the inserted or the updated row.
+            for (inserted, updated) in row.iter_mut().zip(updated) {
+                inserted.combine(updated);
+            }
+        }
+
         if let Some(returning) = &insert.returning {
+            table.add_alias(insert.table_alias.clone());
+            let table_name = table.table_name.clone().unwrap_or_default();
+            context.push(table);
+
             let table = context
-                .source
-                .find_by_original_name(&insert.table_name.0)
+                .find_table_by_idents_table(&table_name)
                 .context("Could not find")?;
-            context.push(table.clone());
+            let table_id = table.table_id;
+            let column_ids: Vec<_> = table.columns.iter().map(|col| col.column_id).collect();
+
+            for (column_id, result) in column_ids.into_iter().zip(row) {
+                if result.always_null {
+                    context.wal.add_null_column(table_id, column_id);
+                } else if let Some(nullable) = result.value {
+                    context.wal.add_column(table_id, column_id, nullable);
+                }
+            }
 
             return context.nullable_for(returning);
         }
         Ok(StatementNullable::new())
     }
 }
+
+/// The nullability of every column of `table` in the inserted rows.
+fn inserted_row(
+    context: &mut Context,
+    insert: &Insert,
+    table: &Table,
+) -> anyhow::Result<Vec<NullableResult>> {
+    // the index of every column of the table in the inserted rows.
+    let positions: Vec<_> = table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(index, col)| {
+            if insert.columns.is_empty() {
+                Some(index)
+            } else {
+                insert
+                    .columns
+                    .iter()
+                    .position(|name| Some(name) == col.column_name.as_ref())
+            }
+        })
+        .collect();
+
+    let defaults: Vec<_> = table.columns.iter().map(default_result).collect();
+
+    let mut rows = Vec::new();
+    match insert.source.as_ref().map(|source| source.body.as_ref()) {
+        // `DEFAULT` is parsed as an identifier.
+        Some(SetExpr::Values(values)) => {
+            for row in &values.rows {
+                let mut nullables = Vec::new();
+                for (index, expr) in row.iter().enumerate() {
+                    if is_default(expr) {
+                        let default = positions
+                            .iter()
+                            .position(|position| *position == Some(index))
+                            .map(|col| defaults[col].clone());
+                        nullables.push(default.unwrap_or(NullableResult::unnamed(Some(true))));
+                    } else {
+                        nullables.push(visit_expr(expr, None, context)?);
+                    }
+                }
+                rows.push(nullables);
+            }
+        }
+        Some(_) => {
+            let source = insert.source.as_ref().context("Could not find")?;
+            rows.push(context.nullable_for(source)?.flatten().into_results());
+        }
+        // `DEFAULT VALUES`
+        None => rows.push(Vec::new()),
+    }
+
+    let row = table
+        .columns
+        .iter()
+        .zip(positions)
+        .zip(defaults)
+        .map(|((col, position), default)| {
+            let mut result = None::<NullableResult>;
+            for row in &rows {
+                let value = position
+                    .and_then(|position| row.get(position).cloned())
+                    .unwrap_or(default.clone());
+                match &mut result {
+                    Some(result) => result.combine(value),
+                    None => result = Some(value),
+                }
+            }
+
+            // the insert fails when a `NOT NULL` column is null.
+            let result = result.unwrap_or(default);
+            if col.catalog_nullable {
+                result.set_alias(col.column_name.clone())
+            } else {
+                NullableResult::new(Some(false), col.column_name.clone())
+            }
+        })
+        .collect();
+
+    Ok(row)
+}
+
+/// The nullability of every column of `table` in the row updated by `ON CONFLICT DO UPDATE`.
+fn updated_row(
+    context: &mut Context,
+    insert: &Insert,
+    table: &Table,
+    inserted: &[NullableResult],
+    do_update: &DoUpdate,
+) -> anyhow::Result<Vec<NullableResult>> {
+    // the existing row has the catalog nullability, `EXCLUDED` is the row that was inserted.
+    let mut existing = table.clone();
+    existing.add_alias(insert.table_alias.clone());
+    context.push(existing);
+    context.push(Nullable::new(inserted.to_vec()).to_table("excluded"));
+
+    let mut row: Vec<_> = table
+        .columns
+        .iter()
+        .map(|col| col.catalog_result())
+        .collect();
+    for (index, value) in context.assigned_values(table, &do_update.assignments)? {
+        row[index] = value;
+    }
+
+    if let Some(selection) = &do_update.selection {
+        visit_expr(selection, None, context)?;
+    }
+
+    Ok(row)
+}
+
+/// The nullability of a column that gets its default value, null without a default and the
+/// catalog nullability when the default is unknown.
+fn default_result(col: &TableColumn) -> NullableResult {
+    match col.default {
+        ColumnDefault::Value(nullable) => {
+            NullableResult::new(Some(nullable), col.column_name.clone())
+        }
+        ColumnDefault::None => NullableResult::always_null(col.column_name.clone()),
+        ColumnDefault::Unknown => {
+            NullableResult::new(Some(col.catalog_nullable), col.column_name.clone())
+        }
+    }
+}
+
+fn is_default(expr: &Expr) -> bool {
+    matches!(expr, Expr::Identifier(ident) if ident.quote_style.is_none() && ident.value == DEFAULT_KEYWORD)
+}
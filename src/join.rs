@@ -1,15 +1,18 @@
 use std::collections::HashSet;
 
-use anyhow::Context as _;
-use sqlparser::ast::{JoinConstraint, JoinOperator, Select};
+use anyhow::{anyhow, Context as _};
+use sqlparser::ast::{JoinConstraint, JoinOperator, TableWithJoins};
 
-use crate::{context::Context, join_resolver::JoinResolver, Table, TableId};
+use crate::{context::Context, join_resolver::JoinResolver, SqlFlavour, Table, TableId};
 
 impl Context {
-    pub fn update_from_join(&mut self, select: &Select) -> anyhow::Result<Vec<JoinResolver>> {
+    pub fn update_from_join(
+        &mut self,
+        from: &[TableWithJoins],
+    ) -> anyhow::Result<Vec<JoinResolver>> {
         let mut resolvers = Vec::new();
 
-        for table in &select.from {
+        for table in from {
             if table.joins.is_empty() {
                 continue;
             }
@@ -29,7 +32,7 @@ impl Context {
                     JoinOperator::LeftOuter(inner) => {
                         self.handle_join_constraint(
                             &mut join_resolver,
-                            &inner,
+                            inner,
                             &base_table,
                             &left_table,
                             |left_table, right_table, resolver| {
@@ -40,12 +43,12 @@ impl Context {
                                 }
                                 resolver.set_nullable(left_table, Some(true));
                             },
-                        );
+                        )?;
                     }
                     JoinOperator::Inner(inner) => {
                         self.handle_join_constraint(
                             &mut join_resolver,
-                            &inner,
+                            inner,
                             &base_table,
                             &left_table,
                             |left_table, right_table, resolver| {
@@ -59,7 +62,7 @@ impl Context {
                                     }
                                 }
                             },
-                        );
+                        )?;
                     }
                     JoinOperator::CrossJoin => {
                         join_resolver.add_leaf(base_table.table_id, left_table.table_id, None);
@@ -68,7 +71,7 @@ impl Context {
                     JoinOperator::RightOuter(inner) => {
                         self.handle_join_constraint(
                             &mut join_resolver,
-                            &inner,
+                            inner,
                             &base_table,
                             &left_table,
                             |left_table, right_table, resolver| {
@@ -81,12 +84,12 @@ impl Context {
                                 }
                                 resolver.set_nullable(left_table, Some(false));
                             },
-                        );
+                        )?;
                     }
                     JoinOperator::FullOuter(inner) => {
                         self.handle_join_constraint(
                             &mut join_resolver,
-                            &inner,
+                            inner,
                             &base_table,
                             &left_table,
                             |left_table, right_table, resolver| {
@@ -101,7 +104,7 @@ impl Context {
                                 }
                                 resolver.set_nullable(left_table, Some(true));
                             },
-                        );
+                        )?;
                     }
                     operator => unimplemented!("{operator:?}"),
                 }
@@ -125,7 +128,7 @@ impl Context {
         base_table: &Table,
         left_joined_table: &Table,
         callback: impl Fn(TableId, &[TableId], &mut JoinResolver),
-    ) {
+    ) -> anyhow::Result<()> {
         match &constraint {
             JoinConstraint::On(expr) => {
                 let mut t = HashSet::new();
@@ -137,7 +140,7 @@ impl Context {
                     .find(|table| **table == left_joined_table.table_id)
                     .unwrap();
 
-                let _ = (callback)(*left_table, &right_tables, join_resolver);
+                (callback)(*left_table, &right_tables, join_resolver);
             }
             JoinConstraint::Using(col_name) => {
                 let right_tables: Vec<_> = self
@@ -155,9 +158,19 @@ impl Context {
                     join_resolver.add_leaf(*right_table, *left_table, None);
                 }
 
-                let _ = (callback)(*left_table, &right_tables, join_resolver);
+                (callback)(*left_table, &right_tables, join_resolver);
             }
-            JoinConstraint::Natural => {
+            // Sqlite allows joins without a constraint, every row of the joined table matches.
+            JoinConstraint::Natural | JoinConstraint::None => {
+                if matches!(constraint, JoinConstraint::None)
+                    && self.flavour == SqlFlavour::Postgres
+                {
+                    return Err(anyhow!(
+                        "join without constraint is not supported in Postgres: {:?}",
+                        left_joined_table.original_name
+                    ));
+                }
+
                 let right_tables = vec![base_table.table_id, left_joined_table.table_id];
 
                 let left_table = right_tables
@@ -169,11 +182,9 @@ impl Context {
                     join_resolver.add_leaf(*right_table, *left_table, None);
                 }
 
-                let _ = (callback)(*left_table, &right_tables, join_resolver);
-            }
-            JoinConstraint::None => {
-                panic!("not sure what to do here?");
+                (callback)(*left_table, &right_tables, join_resolver);
             }
         }
+        Ok(())
     }
 }
@@ -26,7 +26,9 @@ impl JoinResolver {
     pub fn add_leaf(&mut self, base: TableId, leaf_id: TableId, leaf_nullable: Option<bool>) {
         if base == leaf_id {
             return;
-        } else if self.data.table_id == base {
+        }
+
+        if self.data.table_id == base {
             self.leafs.push(JoinResolver {
                 data: JoinEntry {
                     table_id: leaf_id,
@@ -73,7 +75,7 @@ impl JoinResolver {
                 return true;
             }
         }
-        return false;
+        false
     }
 
     // pub fn bubbling_not_null(&mut self, table_id: TableId) {
@@ -1,13 +1,18 @@
 mod context;
+mod create;
 mod cte;
 mod delete;
+mod eval;
 mod expr;
 mod func;
+mod ident;
 mod insert;
 mod join;
 mod join_resolver;
+mod merge;
 mod nullable;
 mod params;
+mod predicate;
 mod query;
 mod select;
 mod select_item;
@@ -16,26 +21,54 @@ mod source;
 mod state;
 mod statement;
 mod table;
+mod update;
 mod values;
 mod wal;
 mod where_;
 
-pub use source::Source;
-use sqlparser::dialect::{Dialect, PostgreSqlDialect, SQLiteDialect};
+pub use nullable::Nullability;
+pub use source::{Domain, Source, View};
+use sqlparser::{
+    ast::Ident,
+    dialect::{Dialect, PostgreSqlDialect, SQLiteDialect},
+};
 pub use state::NullableState;
 pub use table::*;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SqlFlavour {
     Postgres,
     Sqlite,
 }
 
 impl SqlFlavour {
-    fn to_dialect(&self) -> &'static dyn Dialect {
+    pub(crate) fn to_dialect(self) -> &'static dyn Dialect {
         match self {
             SqlFlavour::Postgres => &PostgreSqlDialect {},
             SqlFlavour::Sqlite => &SQLiteDialect {},
         }
     }
+
+    /// The schema of the tables that don't have a schema.
+    fn default_schema(self) -> Ident {
+        match self {
+            SqlFlavour::Postgres => Ident::new("public"),
+            SqlFlavour::Sqlite => Ident::new("main"),
+        }
+    }
+
+    /// The schema of temporary tables, Postgres searches it before the search path.
+    fn temp_schema(self) -> Ident {
+        match self {
+            SqlFlavour::Postgres => Ident::new("pg_temp"),
+            SqlFlavour::Sqlite => Ident::new("temp"),
+        }
+    }
+
+    fn default_search_path(self) -> Vec<Ident> {
+        match self {
+            SqlFlavour::Postgres => vec![Ident::new("public")],
+            SqlFlavour::Sqlite => vec![Ident::new("temp"), Ident::new("main")],
+        }
+    }
 }
@@ -0,0 +1,71 @@
+use sqlparser::ast::{
+    Expr, MergeAction, MergeClause, MergeClauseKind, MergeInsertKind, TableFactor,
+};
+
+use crate::{
+    context::Context,
+    expr::{get_nullable_col, visit_expr},
+    nullable::StatementNullable,
+};
+
+impl Context {
+    pub fn nullable_for_merge(
+        &mut self,
+        table: &TableFactor,
+        source: &TableFactor,
+        on: &Expr,
+        clauses: &[MergeClause],
+    ) -> anyhow::Result<StatementNullable> {
+        self.visit_table_factor(table)?;
+        self.visit_table_factor(source)?;
+
+        let target = self.find_table_by_table_factor(table);
+        let source = self.find_table_by_table_factor(source);
+
+        for clause in clauses {
+            self.scoped(|context| {
+                // the row of the relation without a match is null.
+                let missing = match clause.clause_kind {
+                    MergeClauseKind::Matched => {
+                        get_nullable_col(on, context, &mut [])?;
+                        None
+                    }
+                    MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget => {
+                        target.as_ref()
+                    }
+                    MergeClauseKind::NotMatchedBySource => source.as_ref(),
+                };
+                if let Some(missing) = missing {
+                    for column in &missing.columns {
+                        context
+                            .wal
+                            .add_null_column(missing.table_id, column.column_id);
+                    }
+                }
+
+                if let Some(predicate) = &clause.predicate {
+                    get_nullable_col(predicate, context, &mut [])?;
+                    visit_expr(predicate, None, context)?;
+                }
+
+                match &clause.action {
+                    MergeAction::Update { assignments } => {
+                        if let Some(target) = &target {
+                            context.assigned_values(target, assignments)?;
+                        }
+                    }
+                    MergeAction::Insert(insert) => {
+                        if let MergeInsertKind::Values(values) = &insert.kind {
+                            context.nullable_for(values)?;
+                        }
+                    }
+                    MergeAction::Delete => (),
+                }
+                anyhow::Ok(())
+            })?;
+        }
+
+        // sqlparser doesn't parse the `RETURNING` of `MERGE`.
+        Ok(StatementNullable::new())
+    }
+}
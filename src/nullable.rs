@@ -1,18 +1,35 @@
 use sqlparser::ast::Ident;
 
-use crate::{context::Context, Table, ToOptName};
+use crate::{context::Context, ColumnId, Table, TableColumn, TableId, ToOptName};
+
+/// The inferred nullability of a column in the result set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nullability {
+    NotNull,
+    Nullable,
+    /// The column is null in every row of the result set.
+    AlwaysNull,
+}
 
 #[derive(Debug, Clone)]
 pub struct NullableResult {
     pub column_name: Option<Ident>,
     pub value: Option<bool>,
+    pub always_null: bool,
+    /// Nullability of the elements when the value is an array.
+    pub elements: Option<Box<NullableResult>>,
+    /// Nullability of the fields when the value is a composite.
+    pub fields: Option<Vec<NullableResult>>,
 }
 
 impl NullableResult {
     pub fn new(value: Option<bool>, column_name: Option<Ident>) -> Self {
         Self {
             value,
-            column_name
+            column_name,
+            always_null: false,
+            elements: None,
+            fields: None,
         }
     }
 
@@ -20,6 +37,66 @@ impl NullableResult {
         Self {
             value,
             column_name: None,
+            always_null: false,
+            elements: None,
+            fields: None,
+        }
+    }
+
+    pub fn always_null(column_name: Option<Ident>) -> Self {
+        Self {
+            value: Some(true),
+            column_name,
+            always_null: true,
+            elements: None,
+            fields: None,
+        }
+    }
+
+    pub fn with_elements(mut self, elements: Option<NullableResult>) -> Self {
+        self.elements = elements.map(Box::new);
+        self
+    }
+
+    pub fn with_fields(mut self, fields: Option<Vec<NullableResult>>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// The field of a composite, null when the composite itself is null.
+    pub fn field(&self, name: &Ident) -> Option<NullableResult> {
+        let field = self
+            .fields
+            .as_ref()?
+            .iter()
+            .find(|field| field.column_name.as_ref() == Some(name))?;
+
+        let value = match (self.value, field.value) {
+            (Some(false), value) => value,
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (None, _) => None,
+        };
+
+        Some(NullableResult {
+            value,
+            always_null: self.always_null || field.always_null,
+            ..field.clone()
+        })
+    }
+
+    pub fn element_nullability(&self) -> Option<Nullability> {
+        self.elements
+            .as_ref()
+            .map(|elements| elements.nullability())
+    }
+
+    pub fn nullability(&self) -> Nullability {
+        if self.always_null {
+            Nullability::AlwaysNull
+        } else if self.value.unwrap_or(true) {
+            Nullability::Nullable
+        } else {
+            Nullability::NotNull
         }
     }
 
@@ -36,8 +113,62 @@ impl NullableResult {
             (Some(first), None) => Some(first),
             (None, Some(second)) => Some(second),
             (None, None) => None,
+        };
+        self.always_null = self.always_null && other.always_null;
+        self.combine_nested(other.elements, other.fields);
+    }
+
+    /// Combines the results of columns where every row matches a row of the other column, the
+    /// column is non-null when either is non-null.
+    pub fn intersect(&mut self, other: NullableResult) {
+        self.value = match (self.value, other.value) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        };
+        self.always_null = self.always_null || other.always_null;
+        self.combine_nested(other.elements, other.fields);
+    }
+
+    fn combine_nested(
+        &mut self,
+        elements: Option<Box<NullableResult>>,
+        fields: Option<Vec<NullableResult>>,
+    ) {
+        match (&mut self.elements, elements) {
+            (Some(first), Some(second)) => first.combine(*second),
+            (None, Some(second)) => self.elements = Some(second),
+            _ => (),
+        }
+
+        match (&mut self.fields, fields) {
+            (Some(first), Some(second)) => {
+                for (first, second) in first.iter_mut().zip(second) {
+                    first.combine(second);
+                }
+            }
+            (None, Some(second)) => self.fields = Some(second),
+            _ => (),
         }
     }
+
+    fn into_column(self, column_id: ColumnId) -> TableColumn {
+        let mut column = TableColumn::new(
+            self.column_name,
+            self.value.unwrap_or(true),
+            TableId::new(0),
+            column_id,
+        );
+        column.element_nullable = self.elements.map(|e| e.value.unwrap_or(true));
+        column.fields = self.fields.map(|fields| {
+            fields
+                .into_iter()
+                .enumerate()
+                .map(|(index, field)| field.into_column(ColumnId::new(index)))
+                .collect()
+        });
+        column
+    }
 }
 
 #[derive(Default, Debug, Clone)]
@@ -60,6 +191,7 @@ impl Nullable {
         self.0.push(null);
     }
 
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_result(mut self) -> Option<NullableResult> {
         let mut result = self.0.pop()?;
         for other in self.0 {
@@ -68,22 +200,26 @@ impl Nullable {
         Some(result)
     }
 
+    pub fn into_results(self) -> Vec<NullableResult> {
+        self.0
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut NullableResult> {
         self.0.iter_mut()
     }
 
-    pub fn nullable(&self, col_name: &str, index: usize) -> Option<bool> {
+    pub fn result(&self, col_name: &str, index: usize) -> &NullableResult {
         let col_name = Ident::new(col_name);
 
-        if let Some((left_index, left_nullable)) = self.l_find_index(&col_name) {
+        if let Some((left_index, _left_nullable)) = self.l_find_index(&col_name) {
             if let Some((right_index, _right_nullable)) = self.r_find_index(&col_name) {
                 if left_index == right_index {
-                    return left_nullable;
+                    return &self.0[left_index];
                 }
             }
         }
 
-        self.0[index].value
+        &self.0[index]
     }
 
     pub fn nullable_index(&self, index: usize) -> Option<bool> {
@@ -114,11 +250,23 @@ impl Nullable {
         None
     }
 
+    /// A table named by the last part of `name`, `schema.name` puts the table in the schema.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_schema_table(self, name: &[Ident]) -> Table {
+        let mut table = self.to_table(name[name.len() - 1..].to_vec());
+        if let [schema, _] = name {
+            table.schema = Some(schema.clone());
+        }
+        table
+    }
+
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_table(self, table_name: impl ToOptName) -> Table {
         let mut table = Table::new(table_name);
 
         for row in self.0 {
-            table = table.push_column2(row.column_name, row.value.unwrap_or(true))
+            let column = row.into_column(ColumnId::new(table.columns.len()));
+            table.columns.push(column);
         }
 
         table
@@ -149,10 +297,6 @@ impl StatementNullable {
         self.nullables.push(nullable);
     }
 
-    pub fn combine(&mut self, mut null: Self) {
-        self.nullables.append(&mut null.nullables)
-    }
-
     pub fn get_nullable(mut self) -> Vec<Option<bool>> {
         let Some(mut inferred_nullable): Option<Vec<Option<bool>>> = self
             .nullables
@@ -189,6 +333,9 @@ impl StatementNullable {
                     (None, None) => None,
                 };
 
+                first.0[i].always_null = first.0[i].always_null && col.always_null;
+                first.0[i].combine_nested(col.elements, col.fields);
+
                 if first.0[i].column_name.is_some() {
                     first.0[i].value = value;
                     continue;
@@ -202,16 +349,13 @@ impl StatementNullable {
         first
     }
 
-    pub fn get_nullable_final(self, cols: &[&str]) -> Vec<bool> {
+    pub fn get_results_final(self, cols: &[&str]) -> Vec<NullableResult> {
         let nullables = self.flatten();
 
-        let mut results = Vec::new();
-
-        for (index, col) in cols.iter().enumerate() {
-            results.push(nullables.nullable(col, index).unwrap_or(true));
-        }
-
-        results
+        cols.iter()
+            .enumerate()
+            .map(|(index, col)| nullables.result(col, index).clone())
+            .collect()
     }
 }
 
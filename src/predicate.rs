@@ -0,0 +1,225 @@
+use sqlparser::ast::{BinaryOperator, Expr, UnaryOperator};
+
+use crate::{
+    context::Context,
+    expr::{get_column, visit_expr},
+    TableColumn,
+};
+
+/// Splits a predicate into its conjuncts: `a AND (b AND c)` becomes `[a, b, c]`.
+pub fn conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut exprs = conjuncts(left);
+            exprs.append(&mut conjuncts(right));
+            exprs
+        }
+        Expr::Nested(nested) => conjuncts(nested),
+        expr => vec![expr],
+    }
+}
+
+/// Returns the columns that have to be non null for `expr` to evaluate to `outcome`.
+pub fn non_null_columns(
+    expr: &Expr,
+    outcome: bool,
+    context: &mut Context,
+) -> anyhow::Result<Vec<TableColumn>> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let left = non_null_columns(left, outcome, context)?;
+            let right = non_null_columns(right, outcome, context)?;
+
+            // `a AND b` is true when both are true, false when either one is false.
+            if outcome {
+                Ok(union(left, right))
+            } else {
+                Ok(intersection(left, right))
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Or,
+            right,
+        } => {
+            let left = non_null_columns(left, outcome, context)?;
+            let right = non_null_columns(right, outcome, context)?;
+
+            // `a OR b` is true when either one is true, false when both are false.
+            if outcome {
+                Ok(intersection(left, right))
+            } else {
+                Ok(union(left, right))
+            }
+        }
+        Expr::BinaryOp { left, op, right } if is_strict_operator(op) => {
+            let left = strict_columns(left, context)?;
+            let right = strict_columns(right, context)?;
+            Ok(union(left, right))
+        }
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => non_null_columns(expr, !outcome, context),
+        Expr::Nested(nested) => non_null_columns(nested, outcome, context),
+        Expr::IsNotNull(expr) if outcome => strict_columns(expr, context),
+        Expr::IsNull(expr) if !outcome => strict_columns(expr, context),
+        Expr::IsTrue(expr) if outcome => non_null_columns(expr, true, context),
+        Expr::IsFalse(expr) if outcome => non_null_columns(expr, false, context),
+        Expr::IsNotTrue(expr) if !outcome => non_null_columns(expr, true, context),
+        Expr::IsNotFalse(expr) if !outcome => non_null_columns(expr, false, context),
+        Expr::IsNotDistinctFrom(left, right) if outcome => {
+            let mut columns = Vec::new();
+            if visit_expr(right, None, context)?.value == Some(false) {
+                columns = union(columns, strict_columns(left, context)?);
+            }
+            if visit_expr(left, None, context)?.value == Some(false) {
+                columns = union(columns, strict_columns(right, context)?);
+            }
+            Ok(columns)
+        }
+        // `x IN (..)` is never true or false when `x` is null.
+        Expr::InList { expr, list, .. } => {
+            let mut columns = strict_columns(expr, context)?;
+            if outcome {
+                // one of the values has to match.
+                let mut matched: Option<Vec<TableColumn>> = None;
+                for value in list {
+                    let value = strict_columns(value, context)?;
+                    matched = Some(match matched {
+                        Some(matched) => intersection(matched, value),
+                        None => value,
+                    });
+                }
+                columns = union(columns, matched.unwrap_or_default());
+            }
+            Ok(columns)
+        }
+        // `x IN (subquery)` is false when the subquery is empty, even when `x` is null.
+        Expr::InSubquery { expr, negated, .. } if outcome != *negated => {
+            strict_columns(expr, context)
+        }
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => {
+            let mut columns = strict_columns(expr, context)?;
+            if outcome != *negated {
+                columns = union(columns, strict_columns(low, context)?);
+                columns = union(columns, strict_columns(high, context)?);
+            }
+            Ok(columns)
+        }
+        Expr::Like { expr, pattern, .. }
+        | Expr::ILike { expr, pattern, .. }
+        | Expr::SimilarTo { expr, pattern, .. }
+        | Expr::RLike { expr, pattern, .. } => {
+            let columns = strict_columns(expr, context)?;
+            Ok(union(columns, strict_columns(pattern, context)?))
+        }
+        Expr::Identifier(_) | Expr::CompoundIdentifier(_) => strict_columns(expr, context),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Returns the columns that have to be null for `expr` to evaluate to `outcome`.
+pub fn null_columns(
+    expr: &Expr,
+    outcome: bool,
+    context: &mut Context,
+) -> anyhow::Result<Vec<TableColumn>> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let left = null_columns(left, outcome, context)?;
+            let right = null_columns(right, outcome, context)?;
+
+            if outcome {
+                Ok(union(left, right))
+            } else {
+                Ok(intersection(left, right))
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Or,
+            right,
+        } => {
+            let left = null_columns(left, outcome, context)?;
+            let right = null_columns(right, outcome, context)?;
+
+            if outcome {
+                Ok(intersection(left, right))
+            } else {
+                Ok(union(left, right))
+            }
+        }
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => null_columns(expr, !outcome, context),
+        Expr::Nested(nested) => null_columns(nested, outcome, context),
+        Expr::IsNull(expr) if outcome => Ok(get_column(expr, context)?.into_iter().collect()),
+        Expr::IsNotNull(expr) if !outcome => Ok(get_column(expr, context)?.into_iter().collect()),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Returns the columns that make `expr` null when they are null.
+fn strict_columns(expr: &Expr, context: &mut Context) -> anyhow::Result<Vec<TableColumn>> {
+    match expr {
+        Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+            Ok(get_column(expr, context)?.into_iter().collect())
+        }
+        Expr::BinaryOp { left, op, right } if is_strict_operator(op) => {
+            let left = strict_columns(left, context)?;
+            let right = strict_columns(right, context)?;
+            Ok(union(left, right))
+        }
+        Expr::UnaryOp { expr, .. } => strict_columns(expr, context),
+        Expr::Nested(expr) => strict_columns(expr, context),
+        Expr::Cast { expr, .. } => strict_columns(expr, context),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Operators that return null when one of their operands is null.
+fn is_strict_operator(op: &BinaryOperator) -> bool {
+    !matches!(
+        op,
+        BinaryOperator::And
+            | BinaryOperator::Or
+            | BinaryOperator::Xor
+            | BinaryOperator::Spaceship
+            | BinaryOperator::Custom(_)
+            | BinaryOperator::PGCustomBinaryOperator(_)
+    )
+}
+
+fn union(mut left: Vec<TableColumn>, right: Vec<TableColumn>) -> Vec<TableColumn> {
+    for column in right {
+        if !left.contains(&column) {
+            left.push(column);
+        }
+    }
+    left
+}
+
+fn intersection(left: Vec<TableColumn>, right: Vec<TableColumn>) -> Vec<TableColumn> {
+    left.into_iter()
+        .filter(|column| right.contains(column))
+        .collect()
+}
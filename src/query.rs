@@ -7,10 +7,17 @@ use crate::{
 
 impl GetNullable for Query {
     fn nullable_for(context: &mut Context, query: &Self) -> anyhow::Result<StatementNullable> {
-        if let Some(with) = &query.with {
-            context.add_with(&with)?;
-        }
+        // the ctes are only visible in the query that defines them.
+        let source_len = context.source.len();
 
-        context.nullable_for(&query.body)
+        let nullable = context.scoped(|context| {
+            if let Some(with) = &query.with {
+                context.add_with(with)?;
+            }
+            context.nullable_for(&query.body)
+        });
+
+        context.source.truncate(source_len);
+        nullable
     }
 }
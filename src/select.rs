@@ -1,7 +1,8 @@
-use sqlparser::ast::Select;
+use sqlparser::ast::{Expr, Select, TableWithJoins};
 
 use crate::{
     context::Context,
+    eval::fold_truth,
     nullable::{GetNullable, Nullable},
     select_item::visit_select_item,
 };
@@ -11,33 +12,55 @@ impl GetNullable for Select {
         context: &mut Context,
         select: &Self,
     ) -> anyhow::Result<crate::nullable::StatementNullable> {
-        for table in &select.from {
-            context.add_active_tables(table)?;
+        context.add_from_where(&select.from, select.selection.as_ref())?;
+
+        // `WHERE false` or `WHERE 1 = 0`, aggregates are computed over zero rows.
+        let never_true = select
+            .selection
+            .as_ref()
+            .and_then(fold_truth)
+            .is_some_and(|truth| truth != Some(true));
+        let empty_selection = std::mem::replace(&mut context.empty_selection, never_true);
+
+        let n: anyhow::Result<Vec<_>> = select
+            .projection
+            .iter()
+            .map(|c| visit_select_item(c, context))
+            .collect();
+
+        context.empty_selection = empty_selection;
+
+        Ok(Nullable::new(n?.into_iter().flatten().collect()).into())
+    }
+}
+
+impl Context {
+    /// Adds the tables of a `FROM` clause, with the nullability that follows from their joins
+    /// and from the `WHERE` clause.
+    pub fn add_from_where(
+        &mut self,
+        from: &[TableWithJoins],
+        selection: Option<&Expr>,
+    ) -> anyhow::Result<()> {
+        for table in from {
+            self.add_active_tables(table)?;
         }
 
-        let mut resolvers = context.update_from_join(select)?;
+        let mut resolvers = self.update_from_join(from)?;
         // dbg!(&resolvers);
-        context.update_from_where(select, &mut resolvers)?;
+        self.update_from_where(selection, &mut resolvers)?;
         // dbg!(&resolvers);
-        // dbg!(&context.tables);
-        // dbg!(&context.wal);
+        // dbg!(&self.tables);
+        // dbg!(&self.wal);
 
         for join_resolver in resolvers {
             // dbg!(&join_resolver);
             let join_nullable = join_resolver.get_nullables();
             // dbg!(&join_nullable);
             for (table_id, nullable) in join_nullable {
-                context.wal.add_table(table_id, nullable);
+                self.wal.add_table(table_id, nullable);
             }
         }
-
-        let n: Vec<_> = select
-            .projection
-            .iter()
-            .map(|c| visit_select_item(c, context).unwrap())
-            .flatten()
-            .collect();
-
-        Ok(Nullable::new(n).into())
+        Ok(())
     }
 }
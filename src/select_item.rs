@@ -1,3 +1,4 @@
+use anyhow::Context as _;
 use sqlparser::ast::SelectItem;
 
 use crate::{
@@ -24,16 +25,16 @@ pub fn visit_select_item(
     context: &mut Context,
 ) -> anyhow::Result<Vec<NullableResult>> {
     match select_item {
-        SelectItem::UnnamedExpr(expr) => Ok(vec![visit_expr(&expr, None, context)?]),
+        SelectItem::UnnamedExpr(expr) => Ok(vec![visit_expr(expr, None, context)?]),
         SelectItem::ExprWithAlias { expr, alias } => {
-            Ok(vec![visit_expr(&expr, Some(alias.clone()), context)?])
+            Ok(vec![visit_expr(expr, Some(alias.clone()), context)?])
         }
         SelectItem::Wildcard(_) => {
             let mut results = Vec::new();
 
             for table in context.iter_tables() {
                 for column in table.columns.iter() {
-                    results.push(context.nullable_for_table_col(&table, &column)?);
+                    results.push(context.nullable_for_table_col(table, column)?);
                 }
             }
 
@@ -42,7 +43,9 @@ pub fn visit_select_item(
         SelectItem::QualifiedWildcard(table_name, _wildcard) => {
             let mut results = Vec::new();
 
-            let table = context.find_table_by_idents_table(&table_name.0).unwrap();
+            let table = context
+                .find_table_by_idents_table(&table_name.0)
+                .context(format!("could not find table: {table_name}"))?;
 
             for column in &table.columns {
                 results.push(context.nullable_for_table_col(table, column)?);
@@ -1,6 +1,9 @@
-use sqlparser::ast::SetExpr;
+use sqlparser::ast::{SetExpr, SetOperator, SetQuantifier};
 
-use crate::nullable::{GetNullable, StatementNullable};
+use crate::{
+    context::Context,
+    nullable::{GetNullable, Nullable, NullableResult, StatementNullable},
+};
 
 impl GetNullable for SetExpr {
     fn nullable_for(
@@ -9,11 +12,15 @@ impl GetNullable for SetExpr {
     ) -> anyhow::Result<crate::nullable::StatementNullable> {
         match expr {
             SetExpr::Select(ref select) => context.nullable_for(select),
-            SetExpr::SetOperation { left, right, .. } => {
-                let mut nullable = StatementNullable::new();
-                nullable.combine(context.nullable_for(right)?);
-                nullable.combine(context.nullable_for(left)?);
-                Ok(nullable)
+            SetExpr::SetOperation {
+                op,
+                set_quantifier,
+                left,
+                right,
+            } => {
+                let nullable =
+                    context.nullable_for_set_operation(op, set_quantifier, left, right)?;
+                Ok(StatementNullable::from(nullable))
             }
             SetExpr::Values(values) => context.nullable_for(values),
             SetExpr::Insert(insert) => context.nullable_for(insert),
@@ -23,3 +30,62 @@ impl GetNullable for SetExpr {
         }
     }
 }
+
+impl Context {
+    /// The columns of a set operation are named by the left-most branch, `BY NAME` matches the
+    /// columns of the right branch by name instead of by position.
+    pub fn nullable_for_set_operation(
+        &mut self,
+        op: &SetOperator,
+        set_quantifier: &SetQuantifier,
+        left: &SetExpr,
+        right: &SetExpr,
+    ) -> anyhow::Result<Nullable> {
+        // each branch has its own `FROM`, its tables aren't visible to the other branch.
+        let mut left = self
+            .scoped(|context| context.nullable_for(left))?
+            .flatten()
+            .into_results();
+        let right = self
+            .scoped(|context| context.nullable_for(right))?
+            .flatten()
+            .into_results();
+
+        let right = match set_quantifier {
+            SetQuantifier::ByName | SetQuantifier::AllByName | SetQuantifier::DistinctByName => {
+                by_name(&left, right)
+            }
+            SetQuantifier::All | SetQuantifier::Distinct | SetQuantifier::None => {
+                right.into_iter().map(Some).collect()
+            }
+        };
+
+        for (left, right) in left.iter_mut().zip(right) {
+            match (op, right) {
+                (SetOperator::Union, Some(right)) => left.combine(right),
+                // a row of the right branch without the column.
+                (SetOperator::Union, None) => left.combine(NullableResult::always_null(None)),
+                (SetOperator::Intersect, Some(right)) => left.intersect(right),
+                // the rows only come from the left branch.
+                (SetOperator::Intersect, None) | (SetOperator::Except, _) => (),
+            }
+        }
+
+        Ok(Nullable::new(left))
+    }
+}
+
+fn by_name(left: &[NullableResult], right: Vec<NullableResult>) -> Vec<Option<NullableResult>> {
+    let mut right: Vec<_> = right.into_iter().map(Some).collect();
+    left.iter()
+        .map(|left| {
+            right
+                .iter_mut()
+                .find(|right| {
+                    left.column_name.is_some()
+                        && right.as_ref().map(|right| &right.column_name) == Some(&left.column_name)
+                })
+                .and_then(Option::take)
+        })
+        .collect()
+}
@@ -1,10 +1,23 @@
-use sqlparser::ast::Ident;
+use anyhow::anyhow;
+use sqlparser::{
+    ast::{Ident, ObjectName, Query, Statement, ViewColumnDef},
+    dialect::PostgreSqlDialect,
+    keywords::Keyword,
+    tokenizer::Token,
+};
 
-use crate::Table;
+use crate::{
+    ident::{fold_catalog, parse_sql, parser},
+    SqlFlavour, Table,
+};
 
 #[derive(Debug, Clone)]
 pub struct Source {
     tables: Vec<Table>,
+    domains: Vec<Domain>,
+    views: Vec<View>,
+    search_path: Option<Vec<Ident>>,
+    attached: Vec<Ident>,
     pub params: Vec<bool>,
     pub next_param_index: usize,
 }
@@ -13,6 +26,10 @@ impl Source {
     pub fn new(tables: Vec<Table>) -> Self {
         Source {
             tables,
+            domains: Vec::new(),
+            views: Vec::new(),
+            search_path: None,
+            attached: Vec::new(),
             params: Vec::new(),
             next_param_index: 0,
         }
@@ -21,23 +38,271 @@ impl Source {
     pub fn empty() -> Self {
         Self {
             tables: Vec::new(),
+            domains: Vec::new(),
+            views: Vec::new(),
+            search_path: None,
+            attached: Vec::new(),
             params: Vec::new(),
             next_param_index: 0,
         }
     }
 
-    pub fn find_by_original_name(&self, name: &[Ident]) -> Option<Table> {
-        self.tables
-            .iter()
-            .find(|t| t.original_name.as_deref() == Some(name))
-            .cloned()
+    /// Finds a table by a name that may be qualified by a schema, the tables after `catalog_len`
+    /// are ctes. Unqualified names are searched in the ctes first, then in the schemas of the
+    /// search path, tables without a schema are in the default schema. Names with more than two
+    /// parts, like `catalog.schema.table`, are never found.
+    pub(crate) fn find_table(
+        &self,
+        name: &[Ident],
+        flavour: SqlFlavour,
+        catalog_len: usize,
+    ) -> Option<Table> {
+        let default_schema = flavour.default_schema();
+        let (catalog, ctes) = self.tables.split_at(catalog_len.min(self.tables.len()));
+        let in_schema = |schema: &Ident, table_name: &Ident| {
+            catalog.iter().rev().find(|t| {
+                t.schema.as_ref().unwrap_or(&default_schema) == schema
+                    && t.original_name.as_deref() == Some(std::slice::from_ref(table_name))
+            })
+        };
+
+        let table = match name {
+            [table_name] => ctes
+                .iter()
+                .rev()
+                .find(|t| t.original_name.as_deref() == Some(name))
+                .or_else(|| {
+                    self.search_path(flavour)
+                        .iter()
+                        .find_map(|schema| in_schema(schema, table_name))
+                }),
+            [schema, table_name] => in_schema(schema, table_name),
+            _ => None,
+        };
+
+        let mut table = table.cloned()?;
+        table.schema.get_or_insert(default_schema);
+        Some(table)
+    }
+
+    /// Sets the schemas that unqualified names are searched in.
+    pub fn set_search_path(&mut self, schemas: &[&str]) {
+        self.search_path = Some(schemas.iter().map(|schema| Ident::new(*schema)).collect());
+    }
+
+    /// Attaches a Sqlite database, its tables are searched after `temp` and `main`.
+    pub fn attach(&mut self, database: impl Into<String>) {
+        self.attached.push(Ident::new(database));
+    }
+
+    fn search_path(&self, flavour: SqlFlavour) -> Vec<Ident> {
+        let mut search_path = match &self.search_path {
+            Some(search_path) => search_path.clone(),
+            None => {
+                let mut search_path = flavour.default_search_path();
+                search_path.extend(self.attached.iter().cloned());
+                search_path
+            }
+        };
+
+        // the temporary tables of Postgres are found first, whatever the search path.
+        let temp_schema = flavour.temp_schema();
+        if flavour == SqlFlavour::Postgres && !search_path.contains(&temp_schema) {
+            search_path.insert(0, temp_schema);
+        }
+        search_path
     }
 
     pub fn push(&mut self, table: Table) {
         self.tables.push(table);
     }
 
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Removes the tables that were pushed after the source had `len` tables.
+    pub fn truncate(&mut self, len: usize) {
+        self.tables.truncate(len);
+    }
+
+    pub fn push_domain(&mut self, domain: Domain) {
+        self.domains.push(domain);
+    }
+
+    pub fn find_domain(&self, name: &[Ident]) -> Option<&Domain> {
+        self.domains.iter().find(|d| d.name == name)
+    }
+
+    /// Adds a view, a view with the same name is replaced.
+    pub fn push_view(&mut self, view: View) {
+        self.views.retain(|v| v.name != view.name);
+        self.views.push(view);
+    }
+
+    /// Adds a view defined by the query `sql`.
+    pub fn add_view(&mut self, name: impl Into<String>, sql: impl Into<String>) {
+        self.push_view(View::new(name, sql));
+    }
+
+    /// Finds a view like a table, views without a schema are in the default schema.
+    pub fn find_view(&self, name: &[Ident], flavour: SqlFlavour) -> Option<&View> {
+        let default_schema = flavour.default_schema();
+        let in_schema = |schema: &Ident, view_name: &Ident| {
+            self.views.iter().find(|v| match &v.name[..] {
+                [name] => *schema == default_schema && name == view_name,
+                [view_schema, name] => view_schema == schema && name == view_name,
+                _ => false,
+            })
+        };
+
+        match name {
+            [view_name] => self
+                .search_path(flavour)
+                .iter()
+                .find_map(|schema| in_schema(schema, view_name)),
+            [schema, view_name] => in_schema(schema, view_name),
+            _ => None,
+        }
+    }
+
+    /// Folds the names of the catalog so they compare equal to the identifiers of the queries.
+    pub(crate) fn fold_catalog(&mut self, flavour: SqlFlavour) {
+        let fold_name = |name: &mut Vec<Ident>| {
+            for ident in name.iter_mut() {
+                *ident = fold_catalog(ident, flavour);
+            }
+        };
+
+        for table in self.tables.iter_mut() {
+            table.fold_catalog(flavour);
+        }
+        for view in self.views.iter_mut() {
+            fold_name(&mut view.name);
+            fold_name(&mut view.columns);
+        }
+        for domain in self.domains.iter_mut() {
+            fold_name(&mut domain.name);
+        }
+        if let Some(search_path) = &mut self.search_path {
+            fold_name(search_path);
+        }
+        fold_name(&mut self.attached);
+    }
+
     pub fn add_params(&mut self, mut params: Vec<bool>) {
         self.params.append(&mut params);
     }
 }
+
+/// A view, the nullability of its columns is inferred from its query when it's used.
+#[derive(Debug, Clone)]
+pub struct View {
+    pub name: Vec<Ident>,
+    /// The column names of `CREATE VIEW name (columns) AS ...`.
+    pub columns: Vec<Ident>,
+    pub(crate) query: ViewQuery,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ViewQuery {
+    /// The query is parsed with the flavour of the analysis that uses the view.
+    Sql(String),
+    Parsed(Box<Query>),
+}
+
+impl View {
+    pub fn new(name: impl Into<String>, query: impl Into<String>) -> Self {
+        Self {
+            name: vec![Ident::new(name)],
+            columns: Vec::new(),
+            query: ViewQuery::Sql(query.into()),
+        }
+    }
+
+    /// Reads a view from `CREATE [MATERIALIZED] VIEW name [(columns)] AS query`.
+    pub fn from_create_view(ddl: &str, flavour: SqlFlavour) -> anyhow::Result<Self> {
+        let statement = parse_sql(flavour.to_dialect(), ddl, flavour)?
+            .into_iter()
+            .next();
+        let Some(Statement::CreateView {
+            name,
+            columns,
+            query,
+            ..
+        }) = statement
+        else {
+            return Err(anyhow!("expected `CREATE VIEW`, found: {statement:?}"));
+        };
+
+        Ok(Self::from_query(&name, &columns, query))
+    }
+
+    pub(crate) fn from_query(
+        name: &ObjectName,
+        columns: &[ViewColumnDef],
+        query: Box<Query>,
+    ) -> Self {
+        Self {
+            name: name.0.clone(),
+            columns: columns.iter().map(|column| column.name.clone()).collect(),
+            query: ViewQuery::Parsed(query),
+        }
+    }
+}
+
+/// A domain type, values of a `NOT NULL` domain are never null.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Domain {
+    pub name: Vec<Ident>,
+    pub not_null: bool,
+}
+
+impl Domain {
+    pub fn new(name: impl Into<String>, not_null: bool) -> Self {
+        Self {
+            name: vec![Ident::new(name)],
+            not_null,
+        }
+    }
+
+    /// Reads a domain from `CREATE DOMAIN name [AS] data_type [constraints]`.
+    pub fn from_create_domain(ddl: &str) -> anyhow::Result<Self> {
+        let mut parser = parser(&PostgreSqlDialect {}, ddl, SqlFlavour::Postgres)?;
+
+        // sqlparser doesn't parse `CREATE DOMAIN`.
+        parser.expect_keyword(Keyword::CREATE)?;
+        match parser.next_token().token {
+            Token::Word(word) if word.value.eq_ignore_ascii_case("domain") => (),
+            token => return Err(anyhow!("expected `DOMAIN`, found: {token}")),
+        }
+        let name = parser.parse_object_name(false)?;
+        // `AS` is optional.
+        let _ = parser.parse_keyword(Keyword::AS);
+        parser.parse_data_type()?;
+
+        // `NOT NULL` inside of a `CHECK` doesn't count.
+        let mut not_null = false;
+        let mut depth = 0;
+        loop {
+            match parser.next_token().token {
+                Token::EOF | Token::SemiColon => break,
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                Token::Word(word) if depth == 0 && word.keyword == Keyword::NOT => {
+                    not_null |= parser.parse_keyword(Keyword::NULL);
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            name: name.0,
+            not_null,
+        })
+    }
+}
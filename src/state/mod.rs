@@ -1,8 +1,17 @@
 use std::time::Instant;
 
-use sqlparser::{ast::Statement, parser::Parser};
+use anyhow::Context as _;
 
-use crate::{context::Context, source::Source, wal::Wal, SqlFlavour, Tables};
+use sqlparser::ast::{Ident, Statement};
+
+use crate::{
+    context::Context,
+    ident::{fold_catalog, parse_sql},
+    nullable::{Nullability, NullableResult},
+    source::Source,
+    wal::Wal,
+    SqlFlavour, Tables,
+};
 
 pub struct NullableState {
     parsed_query: Vec<Statement>,
@@ -13,8 +22,9 @@ pub struct NullableState {
 }
 
 impl NullableState {
-    pub fn new(query: &str, source: Source, flavour: SqlFlavour) -> Self {
-        let query = Parser::parse_sql(flavour.to_dialect(), query).unwrap();
+    pub fn new(query: &str, mut source: Source, flavour: SqlFlavour) -> Self {
+        let query = parse_sql(flavour.to_dialect(), query, flavour).unwrap();
+        source.fold_catalog(flavour);
 
         Self {
             parsed_query: query,
@@ -25,14 +35,72 @@ impl NullableState {
     }
 
     pub fn get_nullable(&mut self, cols: &[&str]) -> Vec<bool> {
+        self.try_get_nullable(cols).unwrap()
+    }
+
+    pub fn get_nullability(&mut self, cols: &[&str]) -> Vec<Nullability> {
+        self.try_get_nullability(cols).unwrap()
+    }
+
+    /// Nullability of the elements of array columns, `None` for columns that aren't arrays.
+    pub fn get_element_nullability(&mut self, cols: &[&str]) -> Vec<Option<Nullability>> {
+        self.try_get_element_nullability(cols).unwrap()
+    }
+
+    /// Like `get_nullable`, but returns the error when the query can't be analysed.
+    pub fn try_get_nullable(&mut self, cols: &[&str]) -> anyhow::Result<Vec<bool>> {
+        Ok(self
+            .try_get_nullability(cols)?
+            .into_iter()
+            .map(|nullability| nullability != Nullability::NotNull)
+            .collect())
+    }
+
+    pub fn try_get_nullability(&mut self, cols: &[&str]) -> anyhow::Result<Vec<Nullability>> {
+        Ok(self
+            .get_results(cols)?
+            .iter()
+            .map(|result| result.nullability())
+            .collect())
+    }
+
+    pub fn try_get_element_nullability(
+        &mut self,
+        cols: &[&str],
+    ) -> anyhow::Result<Vec<Option<Nullability>>> {
+        Ok(self
+            .get_results(cols)?
+            .iter()
+            .map(|result| result.element_nullability())
+            .collect())
+    }
+
+    fn get_results(&mut self, cols: &[&str]) -> anyhow::Result<Vec<NullableResult>> {
         // dbg!(&self.parsed_query);
-        let s = self.parsed_query.first().unwrap();
+        let (last, statements) = self
+            .parsed_query
+            .split_last()
+            .context("query without statements")?;
+
+        // the statements before the last one can add tables to the source.
+        let mut source = self.source.clone();
+        for s in statements {
+            let mut context = Context::new(Tables::new(), source, Wal::new(), self.flavour);
+            context.nullable_for(s)?;
+            source = context.source;
+        }
 
-        let mut context =
-            Context::new(Tables::new(), self.source.clone(), Wal::new(), self.flavour);
+        let mut context = Context::new(Tables::new(), source, Wal::new(), self.flavour);
 
-        let inferred_nullable = context.nullable_for(s).unwrap();
+        let inferred_nullable = context.nullable_for(last)?;
         // println!("{:?}", self.started.elapsed());
-        inferred_nullable.get_nullable_final(cols)
+
+        // the names of the columns are the names of the database.
+        let cols: Vec<_> = cols
+            .iter()
+            .map(|col| fold_catalog(&Ident::new(*col), self.flavour).value)
+            .collect();
+        let cols: Vec<_> = cols.iter().map(String::as_str).collect();
+        Ok(inferred_nullable.get_results_final(&cols))
     }
 }
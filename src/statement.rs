@@ -1,16 +1,31 @@
-use sqlparser::ast::Statement;
+use sqlparser::ast::{CreateTable, Statement};
 
 use crate::{
     context::Context,
+    create::select_into,
     nullable::{GetNullable, StatementNullable},
 };
 
 impl GetNullable for Statement {
     fn nullable_for(context: &mut Context, statement: &Self) -> anyhow::Result<StatementNullable> {
         match statement {
-            Statement::Query(query) => context.nullable_for(query),
+            Statement::Query(query) => match select_into(query) {
+                Some(into) => context.add_table_from_query(&into.name, into.temporary, query),
+                None => context.nullable_for(query),
+            },
+            Statement::CreateTable(CreateTable {
+                name,
+                temporary,
+                query: Some(query),
+                ..
+            }) => context.add_table_from_query(name, *temporary, query),
+            Statement::CreateView {
+                name,
+                columns,
+                query,
+                ..
+            } => context.add_view_from_query(name, columns, query),
             Statement::CreateTable(_)
-            | Statement::CreateView { .. }
             | Statement::CreateIndex(_)
             | Statement::CreateType { .. }
             | Statement::CreateExtension { .. }
@@ -31,14 +46,26 @@ impl GetNullable for Statement {
             | Statement::SetTimeZone { .. }
             | Statement::ShowVariable { .. } => Ok(StatementNullable::new()),
             Statement::Update {
-                table, returning, ..
-            } => {
-                if let Some(returning) = returning {
-                    context.add_active_tables(table)?;
-                    return context.nullable_for(returning);
-                }
-                Ok(StatementNullable::new())
-            }
+                table,
+                assignments,
+                from,
+                selection,
+                returning,
+                ..
+            } => context.nullable_for_update(
+                table,
+                assignments,
+                from.as_ref(),
+                selection.as_ref(),
+                returning.as_ref(),
+            ),
+            Statement::Merge {
+                table,
+                source,
+                on,
+                clauses,
+                ..
+            } => context.nullable_for_merge(table, source, on, clauses),
             Statement::Insert(insert) => context.nullable_for(insert),
             Statement::Delete(delete) => context.nullable_for(delete),
             _ => unimplemented!("{statement:?}"),
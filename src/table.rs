@@ -1,11 +1,25 @@
 use anyhow::{anyhow, Context};
-use sqlparser::ast::{Expr, Ident, Table as ParserTable, TableAlias, TableFactor};
+use sqlparser::{
+    ast::{
+        Expr, Ident, Statement, Table as ParserTable, TableAlias, TableFactor,
+        UserDefinedTypeRepresentation,
+    },
+    dialect::PostgreSqlDialect,
+};
 use std::fmt::Debug;
 
-use crate::nullable::{GetNullable, Nullable, StatementNullable};
+use crate::{
+    ident::{fold_catalog, parse_sql},
+    nullable::{GetNullable, Nullable, NullableResult, StatementNullable},
+    Domain, SqlFlavour,
+};
 
 #[derive(Default, Debug, Clone)]
-pub struct Tables(pub Vec<Table>);
+pub struct Tables {
+    tables: Vec<Table>,
+    /// The index of the first table of every nested scope.
+    scopes: Vec<usize>,
+}
 
 impl Tables {
     pub fn new() -> Self {
@@ -13,43 +27,78 @@ impl Tables {
     }
 
     pub fn find_table_id(&self, table_id: TableId) -> Option<&Table> {
-        self.0.iter().find(|t| t.table_id == table_id)
+        self.tables.iter().find(|t| t.table_id == table_id)
     }
 
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
     }
 
     pub fn get_index(&self, other: &Table) -> Option<usize> {
-        self.0.iter().position(|t| t.equals(other))
+        self.tables.iter().position(|t| t.equals(other))
+    }
+
+    /// Starts a nested scope, its tables shadow the tables of the outer scopes.
+    pub fn enter_scope(&mut self) {
+        self.scopes.push(self.tables.len());
+    }
+
+    /// Removes the tables of the innermost scope.
+    pub fn exit_scope(&mut self) {
+        if let Some(start) = self.scopes.pop() {
+            self.tables.truncate(start);
+        }
+    }
+
+    /// The tables of the innermost scope.
+    pub fn iter_scope(&self) -> impl Iterator<Item = &Table> {
+        let scope = self.scopes.last().copied().unwrap_or(0);
+        self.tables[scope..].iter()
+    }
+
+    /// The tables starting from the innermost scope, in the order they were added within a scope.
+    pub fn iter_scoped(&self) -> impl Iterator<Item = &Table> {
+        let mut bounds = vec![0];
+        bounds.extend(self.scopes.iter().copied());
+        bounds.push(self.tables.len());
+
+        bounds
+            .windows(2)
+            .rev()
+            .flat_map(|bounds| &self.tables[bounds[0]..bounds[1]])
+            .collect::<Vec<_>>()
+            .into_iter()
     }
 
     pub fn push(&mut self, mut table: Table) {
-        for cur_table in self.0.iter() {
+        let scope = self.scopes.last().copied().unwrap_or(0);
+        for cur_table in self.tables[scope..].iter() {
             // don't insert duplicate tables
             if cur_table.equals(&table) {
                 return;
             }
         }
 
-        table.table_id = TableId::new(self.0.len());
+        table.table_id = TableId::new(self.tables.len());
 
         for col in table.columns.iter_mut() {
             col.table_id = table.table_id
         }
 
-        self.0.push(table)
+        self.tables.push(table)
     }
 
     pub fn find_table_by_idents_table(&self, name: &[Ident]) -> Option<&Table> {
-        self.0
-            .iter()
-            .find(|t| t.table_name.as_deref() == Some(name))
+        self.iter_scoped().find(|t| t.is_named(name))
     }
     pub fn find_col_by_idents(&self, name: &[Ident]) -> anyhow::Result<(TableColumn, &Table)> {
         // search for col
         if name.len() == 1 {
-            for table in self.0.iter() {
+            for table in self.iter_scoped() {
                 for col in &table.columns {
                     if col.column_name.as_ref() == Some(&name[0]) {
                         return Ok((col.clone(), table));
@@ -58,11 +107,10 @@ impl Tables {
             }
         }
 
-        // look for original name: `table_alias`.`col_name`
+        // look for original name: `table_alias`.`col_name` or `schema`.`table_name`.`col_name`
         if let Some(table) = self
-            .0
-            .iter()
-            .find(|table| table.table_name.as_deref() == Some(&name[..name.len() - 1][..]))
+            .iter_scoped()
+            .find(|table| table.is_named(&name[..name.len() - 1]))
         {
             if let Some(col) = table
                 .columns
@@ -75,8 +123,7 @@ impl Tables {
 
         // look for original name: `original_table_name`.`col_name`
         if let Some(table) = self
-            .0
-            .iter()
+            .iter_scoped()
             .find(|table| table.original_name.as_deref() == Some(&name[..name.len() - 1][..]))
         {
             if let Some(col) = table
@@ -88,14 +135,14 @@ impl Tables {
             }
         }
 
-        return Err(anyhow!("Not found"));
+        Err(anyhow!("Not found"))
     }
 
     pub fn find_cols_by_idents(&self, name: &[Ident]) -> Vec<(TableColumn, &Table)> {
         let mut tables = Vec::new();
 
         // search for col
-        for table in self.0.iter() {
+        for table in self.iter_scoped() {
             for col in &table.columns {
                 if col.column_name.as_ref() == Some(&name[0]) {
                     tables.push((col.clone(), table));
@@ -113,13 +160,13 @@ impl Tables {
     ) -> anyhow::Result<(TableColumn, Table)> {
         match &expr {
             Expr::CompoundIdentifier(idents) => {
-                self.find_col_by_idents(&idents).map(|t| (t.0, t.1.clone()))
+                self.find_col_by_idents(idents).map(|t| (t.0, t.1.clone()))
             }
             Expr::BinaryOp { left, op: _, right } => {
                 if recursive_left {
-                    return self.table_from_expr(left, recursive_left);
+                    self.table_from_expr(left, recursive_left)
                 } else {
-                    return self.table_from_expr(right, recursive_left);
+                    self.table_from_expr(right, recursive_left)
                 }
             }
             _ => Err(anyhow!("not found")),
@@ -131,8 +178,7 @@ impl Tables {
             TableFactor::Table { name, alias, .. } => {
                 if let Some(alias) = alias {
                     return self
-                        .0
-                        .iter()
+                        .iter_scoped()
                         .find(|t| t.table_name.as_deref() == Some(&[alias.name.clone()][..]))
                         .cloned();
                 }
@@ -146,6 +192,8 @@ impl Tables {
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Table {
     pub table_id: TableId,
+    /// The schema of a catalog table, tables without a schema are in the default schema.
+    pub schema: Option<Ident>,
     pub original_name: Option<Vec<Ident>>,
     pub table_name: Option<Vec<Ident>>,
     pub columns: Vec<TableColumn>,
@@ -156,12 +204,18 @@ impl Table {
         let table_name = table_name.to_op_name();
         Self {
             table_id: TableId::new(0),
+            schema: None,
             table_name: table_name.clone(),
             original_name: table_name,
             columns: Vec::new(),
         }
     }
 
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(Ident::new(schema));
+        self
+    }
+
     pub fn push_column2(mut self, column_name: Option<Ident>, catalog_nullable: bool) -> Self {
         self.columns.push(TableColumn::new(
             column_name,
@@ -171,18 +225,72 @@ impl Table {
         ));
         self
     }
-    pub fn push_column(mut self, column_name: impl Into<String>, catalog_nullable: bool) -> Self {
-        self.columns.push(TableColumn::new(
-            Some(Ident::new(column_name)),
-            catalog_nullable,
+    /// Adds a column built with [`Column`], for columns with elements, fields or a default.
+    pub fn push(mut self, column: Column) -> Self {
+        let mut table_column = TableColumn::new(
+            Some(Ident::new(column.name)),
+            column.catalog_nullable,
             self.table_id,
             ColumnId::new(self.columns.len()),
-        ));
+        );
+        table_column.element_nullable = column.element_nullable;
+        table_column.fields = column.fields;
+        table_column.default = column.default;
+        self.columns.push(table_column);
         self
     }
 
+    /// Reads a composite type from `CREATE TYPE name AS (...)`, the fields of a composite type
+    /// can't be declared `NOT NULL` so they are all nullable.
+    pub fn from_create_type(ddl: &str) -> anyhow::Result<Self> {
+        let statements = parse_sql(&PostgreSqlDialect {}, ddl, SqlFlavour::Postgres)?;
+
+        match statements.first() {
+            Some(Statement::CreateType {
+                name,
+                representation: UserDefinedTypeRepresentation::Composite { attributes },
+            }) => Ok(attributes
+                .iter()
+                .fold(Table::new(name.0.clone()), |table, attribute| {
+                    table.push_column(attribute.name.value.clone(), true)
+                })),
+            _ => Err(anyhow!("expected a composite `CREATE TYPE`: {ddl}")),
+        }
+    }
+
+    pub fn push_column(self, column_name: impl Into<String>, catalog_nullable: bool) -> Self {
+        self.push(Column::new(column_name, catalog_nullable))
+    }
+
+    pub(crate) fn fold_catalog(&mut self, flavour: SqlFlavour) {
+        for name in [&mut self.table_name, &mut self.original_name]
+            .into_iter()
+            .flatten()
+        {
+            for ident in name.iter_mut() {
+                *ident = fold_catalog(ident, flavour);
+            }
+        }
+        if let Some(schema) = &mut self.schema {
+            *schema = fold_catalog(schema, flavour);
+        }
+        for column in self.columns.iter_mut() {
+            column.fold_catalog(flavour);
+        }
+    }
+
     pub fn equals(&self, other: &Self) -> bool {
-        self.table_name == other.table_name
+        self.table_name == other.table_name && self.schema == other.schema
+    }
+
+    /// Whether `name` refers to the table, either by its name or as `schema.original_name`.
+    pub fn is_named(&self, name: &[Ident]) -> bool {
+        match name {
+            [schema, table] if self.schema.as_ref() == Some(schema) => {
+                self.original_name.as_deref() == Some(std::slice::from_ref(table))
+            }
+            _ => self.table_name.as_deref() == Some(name),
+        }
     }
 
     pub fn add_alias(&mut self, alias: impl ToOptName) {
@@ -197,11 +305,78 @@ impl Table {
 pub struct TableColumn {
     pub column_name: Option<Ident>,
     pub catalog_nullable: bool,
+    pub element_nullable: Option<bool>,
+    pub fields: Option<Vec<TableColumn>>,
+    pub default: ColumnDefault,
 
     pub column_id: ColumnId,
     pub table_id: TableId,
 }
 
+/// The default value of a column, used for the columns that an `INSERT` leaves out.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum ColumnDefault {
+    /// The default is unknown, the column has its catalog nullability.
+    #[default]
+    Unknown,
+    /// The column has no default, it's null when it isn't inserted.
+    None,
+    /// The column has a default value with the given nullability.
+    Value(bool),
+}
+
+/// A column to add to a [`Table`] with [`Table::push`].
+#[derive(Clone, Debug)]
+pub struct Column {
+    name: String,
+    catalog_nullable: bool,
+    element_nullable: Option<bool>,
+    fields: Option<Vec<TableColumn>>,
+    default: ColumnDefault,
+}
+
+impl Column {
+    pub fn new(name: impl Into<String>, catalog_nullable: bool) -> Self {
+        Self {
+            name: name.into(),
+            catalog_nullable,
+            element_nullable: None,
+            fields: None,
+            default: ColumnDefault::Unknown,
+        }
+    }
+
+    /// An array column, `element_nullable` is the nullability of the elements in the array.
+    pub fn array(mut self, element_nullable: bool) -> Self {
+        self.element_nullable = Some(element_nullable);
+        self
+    }
+
+    /// A column of a composite type, the columns of `composite` are the fields of the type.
+    pub fn composite(mut self, composite: &Table) -> Self {
+        self.fields = Some(composite.columns.clone());
+        self
+    }
+
+    /// A column of a domain type, a column of a `NOT NULL` domain is never null.
+    pub fn domain(mut self, domain: &Domain) -> Self {
+        self.catalog_nullable &= !domain.not_null;
+        self
+    }
+
+    /// A column with a default value, `nullable` is the nullability of the default.
+    pub fn default(mut self, nullable: bool) -> Self {
+        self.default = ColumnDefault::Value(nullable);
+        self
+    }
+
+    /// A column that is known to have no default value.
+    pub fn no_default(mut self) -> Self {
+        self.default = ColumnDefault::None;
+        self
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct TableId(usize);
 
@@ -233,6 +408,15 @@ impl ColumnId {
 }
 
 impl TableColumn {
+    fn fold_catalog(&mut self, flavour: SqlFlavour) {
+        if let Some(column_name) = &mut self.column_name {
+            *column_name = fold_catalog(column_name, flavour);
+        }
+        for field in self.fields.iter_mut().flatten() {
+            field.fold_catalog(flavour);
+        }
+    }
+
     pub fn new(
         column_name: Option<Ident>,
         catalog_nullable: bool,
@@ -244,8 +428,26 @@ impl TableColumn {
             column_id,
             column_name,
             catalog_nullable,
+            element_nullable: None,
+            fields: None,
+            default: ColumnDefault::Unknown,
         }
     }
+
+    /// The nullability of the column in the catalog, including its elements and fields.
+    pub fn catalog_result(&self) -> NullableResult {
+        let elements = self
+            .element_nullable
+            .map(|element_nullable| NullableResult::unnamed(Some(element_nullable)));
+        let fields = self
+            .fields
+            .as_ref()
+            .map(|fields| fields.iter().map(TableColumn::catalog_result).collect());
+
+        NullableResult::new(Some(self.catalog_nullable), self.column_name.clone())
+            .with_elements(elements)
+            .with_fields(fields)
+    }
 }
 
 pub trait ToOptName {
@@ -294,10 +496,13 @@ impl GetNullable for ParserTable {
         ty: &Self,
     ) -> anyhow::Result<crate::nullable::StatementNullable> {
         if let Some(table_name) = &ty.table_name {
-            let table = context
-                .source
-                .find_by_original_name(&[Ident::new(table_name)])
-                .context("could not find column")?;
+            let name: Vec<_> = ty
+                .schema_name
+                .iter()
+                .chain([table_name])
+                .map(Ident::new)
+                .collect();
+            let table = context.source_table(&name)?;
             context.push(table);
             let mut results = Vec::new();
 
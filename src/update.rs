@@ -0,0 +1,99 @@
+use anyhow::Context as _;
+use sqlparser::ast::{Assignment, AssignmentTarget, Expr, SelectItem, TableWithJoins};
+
+use crate::{
+    context::Context,
+    expr::visit_expr,
+    nullable::{NullableResult, StatementNullable},
+    Table,
+};
+
+impl Context {
+    pub fn nullable_for_update(
+        &mut self,
+        table: &TableWithJoins,
+        assignments: &[Assignment],
+        from: Option<&TableWithJoins>,
+        selection: Option<&Expr>,
+        returning: Option<&Vec<SelectItem>>,
+    ) -> anyhow::Result<StatementNullable> {
+        // the tables of `FROM` are joined with the updated table.
+        let mut tables = vec![table.clone()];
+        tables.extend(from.cloned());
+        self.add_from_where(&tables, selection)?;
+
+        let updated = self
+            .find_table_by_table_factor(&table.relation)
+            .context(format!("Could not find {:?}", table.relation))?;
+
+        // all of the assignments see the row from before the update.
+        let assigned = self.assigned_values(&updated, assignments)?;
+        for (index, value) in assigned {
+            let column = &updated.columns[index];
+            if value.always_null {
+                self.wal.add_null_column(updated.table_id, column.column_id);
+            } else {
+                // an unknown value overrides the facts of `WHERE` with the catalog nullability.
+                let nullable = value.value.unwrap_or(column.catalog_nullable);
+                self.wal
+                    .add_column(updated.table_id, column.column_id, nullable);
+            }
+        }
+
+        match returning {
+            Some(returning) => self.nullable_for(returning),
+            None => Ok(StatementNullable::new()),
+        }
+    }
+
+    /// The nullability of the values that `SET` assigns to the columns of `table`, by the index
+    /// of the column.
+    pub fn assigned_values(
+        &mut self,
+        table: &Table,
+        assignments: &[Assignment],
+    ) -> anyhow::Result<Vec<(usize, NullableResult)>> {
+        let mut assigned = Vec::new();
+
+        for assignment in assignments {
+            let value = visit_expr(&assignment.value, None, self)?;
+
+            let targets = match &assignment.target {
+                AssignmentTarget::ColumnName(name) => vec![(name.0.last(), value)],
+                // `SET (a, b) = (1, 2)`
+                AssignmentTarget::Tuple(names) => names
+                    .iter()
+                    .enumerate()
+                    .map(|(index, name)| {
+                        let field = value.fields.as_ref().and_then(|f| f.get(index)).cloned();
+                        (
+                            name.0.last(),
+                            field.unwrap_or(NullableResult::unnamed(None)),
+                        )
+                    })
+                    .collect(),
+            };
+
+            for (name, value) in targets {
+                let Some(index) = table
+                    .columns
+                    .iter()
+                    .position(|col| col.column_name.as_ref() == name)
+                else {
+                    continue;
+                };
+
+                // the update fails when a `NOT NULL` column is set to null.
+                let column = &table.columns[index];
+                let value = if column.catalog_nullable {
+                    value.set_alias(column.column_name.clone())
+                } else {
+                    NullableResult::new(Some(false), column.column_name.clone())
+                };
+                assigned.push((index, value));
+            }
+        }
+
+        Ok(assigned)
+    }
+}
@@ -13,7 +13,7 @@ impl GetNullable for Values {
             let mut nullables = Nullable::empty();
 
             for col in row {
-                let nullable = visit_expr(&col, None, context)?;
+                let nullable = visit_expr(col, None, context)?;
                 nullables.push(nullable);
             }
             statement.push(nullables);
@@ -11,6 +11,10 @@ pub enum WalEntry {
         column_id: ColumnId,
         nullable: bool,
     },
+    ColumnNull {
+        table_id: TableId,
+        column_id: ColumnId,
+    },
 }
 
 #[derive(Debug)]
@@ -36,6 +40,13 @@ impl Wal {
         });
     }
 
+    pub fn add_null_column(&mut self, table_id: TableId, column_id: ColumnId) {
+        self.data.push(WalEntry::ColumnNull {
+            table_id,
+            column_id,
+        });
+    }
+
     pub fn nullable_for_col(&self, table: &Table, _column_id: ColumnId) -> Option<bool> {
         for row in self.data.iter().rev() {
             match row {
@@ -46,9 +57,31 @@ impl Wal {
                 } if *table_id == table.table_id && *column_id == _column_id => {
                     return Some(*nullable)
                 }
+                WalEntry::ColumnNull {
+                    table_id,
+                    column_id,
+                } if *table_id == table.table_id && *column_id == _column_id => return Some(true),
                 _ => continue,
             }
         }
         None
     }
+
+    pub fn always_null_for_col(&self, table: &Table, _column_id: ColumnId) -> bool {
+        for row in self.data.iter().rev() {
+            match row {
+                WalEntry::ColumnNullable {
+                    table_id,
+                    column_id,
+                    ..
+                } if *table_id == table.table_id && *column_id == _column_id => return false,
+                WalEntry::ColumnNull {
+                    table_id,
+                    column_id,
+                } if *table_id == table.table_id && *column_id == _column_id => return true,
+                _ => continue,
+            }
+        }
+        false
+    }
 }
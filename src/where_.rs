@@ -1,14 +1,14 @@
-use sqlparser::ast::Select;
+use sqlparser::ast::Expr;
 
 use crate::{context::Context, expr::get_nullable_col, join_resolver::JoinResolver};
 
 impl Context {
     pub fn update_from_where(
         &mut self,
-        select: &Select,
+        selection: Option<&Expr>,
         join_resolvers: &mut [JoinResolver],
     ) -> anyhow::Result<()> {
-        let Some(ref selection) = select.selection else {
+        let Some(selection) = selection else {
             return Ok(());
         };
 
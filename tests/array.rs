@@ -0,0 +1,166 @@
+use nullable::{Column, Nullability, NullableState, Source, SqlFlavour, Table};
+
+#[test]
+pub fn array_literal() {
+    let source = Source::empty();
+
+    let query = r#"
+        select array[1, null] as a, array[1, 2] as b
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b"]);
+    let elements = state.get_element_nullability(&["a", "b"]);
+    println!("{:?} {:?}", nullable, elements);
+    assert!(nullable == [false, false]);
+    assert!(elements == [Some(Nullability::Nullable), Some(Nullability::NotNull)])
+}
+
+#[test]
+pub fn array_agg_elements() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            array_agg(id) as a,
+            array_agg(name) as b,
+            array_remove(array_agg(name), null) as c,
+            array_append(array_agg(id), name) as d,
+            array(select id from users) as e
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let elements = state.get_element_nullability(&["a", "b", "c", "d", "e"]);
+    println!("{:?}", elements);
+    assert!(
+        elements
+            == [
+                Some(Nullability::NotNull),
+                Some(Nullability::Nullable),
+                Some(Nullability::NotNull),
+                Some(Nullability::Nullable),
+                Some(Nullability::NotNull),
+            ]
+    )
+}
+
+#[test]
+pub fn array_column() {
+    let post_table = Table::new("posts")
+        .push_column("id", false)
+        .push(Column::new("tags", false).array(false))
+        .push(Column::new("scores", true).array(true));
+    let source = Source::new(vec![post_table]);
+
+    let query = r#"
+        select tags, scores, unnest(tags) as tag, unnest(scores) as score from posts
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["tags", "scores", "tag", "score"]);
+    let elements = state.get_element_nullability(&["tags", "scores", "tag", "score"]);
+    println!("{:?} {:?}", nullable, elements);
+    assert!(nullable == [false, true, false, true]);
+    assert!(
+        elements
+            == [
+                Some(Nullability::NotNull),
+                Some(Nullability::Nullable),
+                None,
+                None
+            ]
+    )
+}
+
+#[test]
+pub fn unnest_elements() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select u from unnest(array[1, null]) as u
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["u"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true]);
+
+    let query = r#"
+        select * from unnest(array_remove(array[1, null], null))
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["unnest"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
+}
+
+#[test]
+pub fn any_with_null_elements() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push(Column::new("tags", false).array(true));
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select id = any(array[1, 2]) as a, id = any(array[1, null]) as b, 'x' = any(tags) as c
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, true])
+}
+
+#[test]
+pub fn subscript() {
+    let post_table = Table::new("posts")
+        .push_column("id", false)
+        .push(Column::new("tags", false).array(false))
+        .push(Column::new("scores", true).array(false))
+        .push_column("attributes", false);
+    let source = Source::new(vec![post_table]);
+
+    let query = r#"
+        select
+            tags[1] as a,
+            tags[1:2] as b,
+            scores[2:] as c,
+            tags[:id] as d,
+            attributes -> 'color' as e,
+            (array[1, 2])[1] as f
+        from posts
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c", "d", "e", "f"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, false, true, false, true, true])
+}
+
+#[test]
+pub fn subscript_column_name() {
+    let post_table = Table::new("posts")
+        .push_column("id", false)
+        .push(Column::new("tags", false).array(false));
+    let source = Source::new(vec![post_table]);
+
+    let query = r#"
+        select tags[1], tags[1:2] from posts
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["tags", "tags"]);
+    let elements = state.get_element_nullability(&["tags", "tags"]);
+    println!("{:?} {:?}", nullable, elements);
+    assert!(nullable == [true, false]);
+    assert!(elements == [None, Some(Nullability::NotNull)])
+}
@@ -0,0 +1,110 @@
+use nullable::{Column, NullableState, Source, SqlFlavour, Table};
+
+#[test]
+pub fn composite_column() {
+    let address = Table::new("address")
+        .push_column("street", true)
+        .push_column("city", false);
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push(Column::new("home", false).composite(&address))
+        .push(Column::new("work", true).composite(&address));
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            (home).street as a,
+            (home).city as b,
+            (work).city as c,
+            (users.home).city as d
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c", "d"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, false, true, false])
+}
+
+#[test]
+pub fn composite_from_create_type() {
+    let address =
+        Table::from_create_type("create type address as (street text, city text)").unwrap();
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push(Column::new("home", false).composite(&address));
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select (home).city from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["city"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true])
+}
+
+#[test]
+pub fn row_fields() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            row(id, name) as r,
+            (row(id, name)).f1 as a,
+            (row(id, name)).f2 as b,
+            ((id, name)).f1 as c
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["r", "a", "b", "c"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true, false])
+}
+
+#[test]
+pub fn whole_row_reference() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("pet_id", true);
+    let pets_table = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", false);
+    let source = Source::new(vec![user_table, pets_table]);
+
+    let query = r#"
+        select users, pets, (pets).pet_name
+        from users
+        left join pets on pets.pet_id = users.pet_id
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["users", "pets", "pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, true])
+}
+
+#[test]
+pub fn composite_through_subquery() {
+    let address = Table::new("address")
+        .push_column("street", true)
+        .push_column("city", false);
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push(Column::new("home", false).composite(&address));
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select (u.home).city, (u.home).street from (select home from users) as u
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["city", "street"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
@@ -1,4 +1,4 @@
-use nullable::{NullableState, Source, SqlFlavour};
+use nullable::{NullableState, Source, SqlFlavour, Table};
 
 #[test]
 pub fn create_1() {
@@ -11,5 +11,116 @@ pub fn create_1() {
     let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
     let nullable = state.get_nullable(&[]);
     println!("{:?}", nullable);
-    assert!(nullable == [])
+    assert!(nullable.is_empty())
+}
+
+#[test]
+pub fn create_table_as() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+    let pets_table = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("owner_id", false);
+
+    let source = Source::new(vec![users_table, pets_table]);
+
+    let query = r#"
+        create temp table user_pets as
+            select users.id, users.emailadres, pets.pet_id from users left join pets on pets.owner_id = users.id;
+        select id, emailadres, pet_id from user_pets;
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "emailadres", "pet_id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, true])
+}
+
+#[test]
+pub fn select_into() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        select id, coalesce(emailadres, '') as email, null as deleted_at into user_emails from users;
+        select id, email, deleted_at from user_emails;
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "email", "deleted_at"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true])
+}
+
+#[test]
+pub fn select_into_is_empty() {
+    let users_table = Table::new("users").push_column("id", false);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        select id into user_ids from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&[]);
+    println!("{:?}", nullable);
+    assert!(nullable.is_empty())
+}
+
+#[test]
+pub fn create_table_as_in_schema() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        create table public.user_emails as select id, coalesce(emailadres, '') as email from users;
+        create table app.user_ids as select id from users;
+        select e.id, public.user_emails.email, app.user_ids.id as user_id
+        from user_emails e
+        inner join public.user_emails on public.user_emails.id = e.id
+        inner join app.user_ids on app.user_ids.id = e.id;
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "email", "user_id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false])
+}
+
+#[test]
+pub fn create_temp_table_as() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    // the temporary table shadows `users`.
+    let query = r#"
+        create temp table users as select id, 'me' as emailadres from users;
+        select users.emailadres, pg_temp.users.id from users;
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["emailadres", "id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false]);
+
+    let query = r#"
+        create temp table user_ids as select id from users;
+        select temp.user_ids.id from temp.user_ids;
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
 }
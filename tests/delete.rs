@@ -19,3 +19,29 @@ pub fn delete_1() {
     println!("{:?}", nullable);
     assert!(nullable == [false, false, true, false])
 }
+
+#[test]
+pub fn delete_using() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+    let pets_table = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("owner_id", true)
+        .push_column("pet_name", true)
+        .push_column("nickname", true);
+
+    let source = Source::new(vec![users_table, pets_table]);
+
+    let query = r#"
+        delete from pets
+        using users u
+        where pets.owner_id = u.id and pets.nickname is not null
+        returning pets.pet_id, pets.pet_name, pets.nickname, u.emailadres, u.id
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_id", "pet_name", "nickname", "emailadres", "id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false, true, false])
+}
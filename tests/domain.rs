@@ -0,0 +1,74 @@
+use nullable::{Column, Domain, NullableState, Source, SqlFlavour, Table};
+
+#[test]
+pub fn cast_to_domain() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("email", true);
+    let mut source = Source::new(vec![user_table]);
+    source.push_domain(Domain::new("email_address", true));
+    source.push_domain(Domain::new("maybe_email", false));
+
+    let query = r#"
+        select
+            email::email_address as a,
+            cast(email as maybe_email) as b,
+            email::text as c,
+            try_cast(id as email_address) as d
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c", "d"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, true, true])
+}
+
+#[test]
+pub fn domain_from_ddl() {
+    let not_null = Domain::from_create_domain(
+        "create domain email_address as text not null check (value like '%@%')",
+    )
+    .unwrap();
+    let nullable = Domain::from_create_domain(
+        "create domain positive int check (value is not null and value > 0)",
+    )
+    .unwrap();
+    let constraint =
+        Domain::from_create_domain("create domain code varchar(10) constraint c not null").unwrap();
+
+    assert!(not_null == Domain::new("email_address", true));
+    assert!(nullable == Domain::new("positive", false));
+    assert!(constraint == Domain::new("code", true));
+    assert!(Domain::from_create_domain("create table users (id int)").is_err());
+}
+
+#[test]
+pub fn domain_column() {
+    let email = Domain::from_create_domain("create domain email_address text not null").unwrap();
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push(Column::new("email", true).domain(&email));
+    let pets_table = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("user_id", false);
+    let source = Source::new(vec![user_table, pets_table]);
+
+    let query = r#"
+        select users.email from pets left join users on users.id = pets.user_id
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["email"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true]);
+
+    let query = r#"
+        select email from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["email"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
+}
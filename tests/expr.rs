@@ -0,0 +1,255 @@
+use nullable::{Nullability, NullableState, Source, SqlFlavour, Table};
+
+#[test]
+pub fn constant_and_false() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("active", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select active and false as a, active or true as b, active and true as c from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true])
+}
+
+#[test]
+pub fn constant_arithmetic() {
+    let source = Source::empty();
+
+    let query = r#"
+        select 1 + 2 as a, 1 + null as b, (1 = 1) and true as c
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullability(&["a", "b", "c"]);
+    println!("{:?}", nullable);
+    assert!(
+        nullable
+            == [
+                Nullability::NotNull,
+                Nullability::AlwaysNull,
+                Nullability::NotNull
+            ]
+    )
+}
+
+#[test]
+pub fn unary_op() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("active", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select -id as a, not active as b, not false as c from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false])
+}
+
+#[test]
+pub fn between_like() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("age", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            id between 1 and 10 as a,
+            age between 1 and 10 as b,
+            name like 'a%' as c,
+            name ilike 'a%' as d,
+            name similar to 'a%' as e
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c", "d", "e"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false, false, false])
+}
+
+#[test]
+pub fn cast_kinds() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("age", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select cast(id as text) as a, cast(age as text) as b, try_cast(id as int) as c from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, true])
+}
+
+#[test]
+pub fn string_functions_syntax() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("nickname", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            substring(name from 1 for 2) as a,
+            substring(nickname from 1) as b,
+            trim(both 'x' from name) as c,
+            position('a' in nickname) as d,
+            overlay(name placing 'x' from 1 for 2) as e
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c", "d", "e"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false, true, false])
+}
+
+#[test]
+pub fn date_time_syntax() {
+    let event_table = Table::new("events")
+        .push_column("id", false)
+        .push_column("started_at", false)
+        .push_column("ended_at", true);
+    let source = Source::new(vec![event_table]);
+
+    let query = r#"
+        select
+            extract(year from started_at) as a,
+            extract(year from ended_at) as b,
+            started_at at time zone 'utc' as c,
+            started_at + interval '1 day' as d,
+            date '2024-01-01' as e,
+            ceil(1.5) as f,
+            floor(1.5) as g
+        from events
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c", "d", "e", "f", "g"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false, false, false, false, false])
+}
+
+#[test]
+pub fn any_all_collate() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("age", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            id = any(array[1, 2]) as a,
+            age > all(array[1, 2]) as b,
+            name collate "C" as c
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false])
+}
+
+#[test]
+pub fn case_expression() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("age", true);
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            case when id > 1 then 'a' else 'b' end as a,
+            case when id > 1 then 'a' end as b,
+            case id when 1 then age else 0 end as c
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, true])
+}
+
+#[test]
+pub fn json_operators() {
+    let doc_table = Table::new("documents")
+        .push_column("id", false)
+        .push_column("data", false);
+    let source = Source::new(vec![doc_table]);
+
+    let query = r#"
+        select
+            data -> 'user' as a,
+            data ->> 'name' as b,
+            data #> '{user,name}' as c,
+            data #>> '{user,name}' as d,
+            data @> '{"a": 1}' as e,
+            data ? 'name' as f,
+            jsonb_build_object('id', id) as g,
+            jsonb_path_query_first(data, '$.name') as h
+        from documents
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b", "c", "d", "e", "f", "g", "h"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, true, true, true, false, false, false, true])
+}
+
+#[test]
+pub fn json_aggregates() {
+    let doc_table = Table::new("documents")
+        .push_column("id", false)
+        .push_column("data", true);
+    let source = Source::new(vec![doc_table]);
+
+    let query = r#"
+        select jsonb_agg(data) as a, json_object_agg(id, data) as b from documents
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false])
+}
+
+#[test]
+pub fn json_sqlite() {
+    let doc_table = Table::new("documents")
+        .push_column("id", false)
+        .push_column("data", false);
+    let source = Source::new(vec![doc_table]);
+
+    let query = r#"
+        select
+            json_extract(data, '$.name') as a,
+            json_object('id', id) as b,
+            json_group_array(data) as c,
+            data ->> '$.name' as d
+        from documents
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["a", "b", "c", "d"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, false, false, true])
+}
@@ -47,3 +47,259 @@ pub fn func2() {
     println!("{:?}", nullable);
     assert!(nullable == [false])
 }
+
+#[test]
+pub fn null_handling_funcs() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("nickname", true);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            nullif(name, ''),
+            coalesce(nickname, name),
+            greatest(nickname, name),
+            least(nickname, null)
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["nullif", "coalesce", "greatest", "least"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, false, false, true])
+}
+
+#[test]
+pub fn null_handling_funcs_sqlite() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("nickname", true);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            ifnull(nickname, name),
+            iif(id > 1, name, 'none'),
+            iif(id > 1, nickname, name),
+            nullif(id, 1)
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["ifnull", "iif", "iif", "nullif"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true, true])
+}
+
+#[test]
+pub fn nvl() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("nickname", true);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            nvl(nickname, name),
+            nvl(nickname, null),
+            nvl2(nickname, name, 'none'),
+            nvl2(name, nickname, 'none')
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["nvl", "nvl", "nvl2", "nvl2"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false, true])
+}
+
+#[test]
+pub fn postgres_string_funcs() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("nickname", true);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            concat(name, nickname),
+            concat_ws(',', name, nickname),
+            format('%s %s', name, nickname),
+            split_part(name, ' ', 1),
+            replace(nickname, 'a', 'b'),
+            regexp_match(name, 'a+'),
+            pg_catalog.upper(name),
+            array_to_string(array[name, 'x'], ',')
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&[
+        "concat",
+        "concat_ws",
+        "format",
+        "split_part",
+        "replace",
+        "regexp_match",
+        "upper",
+        "array_to_string",
+    ]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, false, true, true, false, false])
+}
+
+#[test]
+pub fn postgres_format_null_string() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("nickname", true);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            format(name, nickname),
+            format(nickname, name)
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["format", "format"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn postgres_ordered_set_aggregates() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("age", true);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            percentile_cont(0.5) within group (order by age),
+            percentile_disc(0.5) within group (order by id),
+            mode() within group (order by age)
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["percentile_cont", "percentile_disc", "mode"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, true, true])
+}
+
+#[test]
+pub fn postgres_misc_funcs() {
+    let event_table = Table::new("events")
+        .push_column("id", false)
+        .push_column("started_at", false)
+        .push_column("ended_at", true);
+
+    let source = Source::new(vec![event_table]);
+
+    let query = r#"
+        select
+            gen_random_uuid(),
+            date_trunc('day', started_at),
+            age(ended_at, started_at),
+            make_date(2024, 1, 1),
+            jsonb_build_object('id', id, 'ended_at', ended_at),
+            array_length(array[id], 1),
+            row_number() over (order by id),
+            lag(id) over (order by id)
+        from events
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&[
+        "gen_random_uuid",
+        "date_trunc",
+        "age",
+        "make_date",
+        "jsonb_build_object",
+        "array_length",
+        "row_number",
+        "lag",
+    ]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true, false, false, true, false, true])
+}
+
+#[test]
+pub fn sqlite_funcs() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("nickname", true);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            max(id, 1),
+            max(id, nickname),
+            typeof(nickname),
+            quote(nickname),
+            hex(nickname),
+            instr(name, 'a'),
+            printf('%s', nickname),
+            datetime(name),
+            last_insert_rowid(),
+            sqrt(id)
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&[
+        "max",
+        "max",
+        "typeof",
+        "quote",
+        "hex",
+        "instr",
+        "printf",
+        "datetime",
+        "last_insert_rowid",
+        "sqrt",
+    ]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false, false, false, false, false, true, false, true])
+}
+
+#[test]
+pub fn sqlite_aggregates() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("nickname", true);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            max(id),
+            total(nickname),
+            group_concat(name, ','),
+            group_concat(nickname, ','),
+            count(*)
+        from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["max", "total", "group_concat", "group_concat", "count"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, true, false])
+}
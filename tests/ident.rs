@@ -0,0 +1,108 @@
+use nullable::{Column, NullableState, Source, SqlFlavour, Table};
+
+#[test]
+pub fn postgres_unquoted_folded() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        SELECT ID, Users.EmailAdres, U.Id AS "Id" FROM Users INNER JOIN "users" u ON u.ID = USERS.id
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "emailadres", "Id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false])
+}
+
+#[test]
+pub fn postgres_quoted_case_sensitive() {
+    let users_table = Table::new("Users")
+        .push_column("Id", false)
+        .push_column("id", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        select "Id", id from "Users"
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["Id", "id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+#[should_panic]
+pub fn postgres_quoted_not_folded() {
+    let users_table = Table::new("users").push_column("id", false);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        select id from "Users"
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    state.get_nullable(&["id"]);
+}
+
+#[test]
+pub fn sqlite_case_insensitive() {
+    let users_table = Table::new("Users")
+        .push_column("ID", false)
+        .push_column("EmailAdres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        select "id", [emailadres], `USERS`.Id as UserId from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["id", "EmailAdres", "UserId"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false])
+}
+
+#[test]
+pub fn quoted_default() {
+    let settings_table = Table::new("settings")
+        .push_column("id", false)
+        .push_column("default", true)
+        .push(Column::new("value", true).default(false));
+
+    let source = Source::new(vec![settings_table]);
+
+    let query = r#"
+        select "default" from settings
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["default"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true]);
+
+    let query = r#"
+        insert into settings (id, value) values (1, DEFAULT), (2, default) returning value
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["value"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false]);
+
+    // a quoted identifier isn't the `DEFAULT` keyword, and there are no columns in `VALUES`.
+    let query = r#"
+        insert into settings (id, value) values (1, "default") returning value
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.try_get_nullable(&["value"]);
+    println!("{:?}", nullable);
+    assert!(nullable.is_err())
+}
@@ -1,4 +1,4 @@
-use nullable::{NullableState, Source, SqlFlavour, Table};
+use nullable::{Column, Nullability, NullableState, Source, SqlFlavour, Table};
 
 #[test]
 pub fn insert_1() {
@@ -17,3 +17,160 @@ insert into pets(pet_name) values ('pet 1'), ('pet 2'), ('pet 3'), ('pet 4') ret
     println!("{:?}", nullable);
     assert!(nullable == [false, false])
 }
+
+#[test]
+pub fn insert_values_returning() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true)
+        .push_column("nickname", true)
+        .push(Column::new("species", true).default(false))
+        .push_column("owner", true)
+        .push(Column::new("vet", true).no_default());
+
+    let mut source = Source::new(vec![table_1]);
+    source.add_params(vec![false]);
+
+    let query = r#"
+insert into pets(pet_name, nickname, species)
+values ('pet 1', $1, default), ('pet 2', $1, 'cat')
+returning pet_id, pet_name, nickname, species, owner, vet;
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable =
+        state.get_nullability(&["pet_id", "pet_name", "nickname", "species", "owner", "vet"]);
+    println!("{:?}", nullable);
+    assert!(
+        nullable
+            == [
+                Nullability::NotNull,
+                Nullability::NotNull,
+                Nullability::NotNull,
+                Nullability::NotNull,
+                Nullability::Nullable,
+                Nullability::AlwaysNull
+            ]
+    )
+}
+
+#[test]
+pub fn insert_array_column_with_default() {
+    let post_table = Table::new("posts")
+        .push_column("id", false)
+        .push(Column::new("tags", true).array(false).default(false));
+
+    let source = Source::new(vec![post_table]);
+
+    let query = r#"
+insert into posts(id) values (1) returning tags, unnest(tags) as tag;
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["tags", "tag"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false])
+}
+
+#[test]
+pub fn insert_values_nullable_row() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+insert into pets(pet_name) values ('pet 1'), (null) returning *;
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_id", "pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn insert_select_returning() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+    let table_2 = Table::new("animals")
+        .push_column("id", false)
+        .push_column("name", false)
+        .push_column("tag", true);
+
+    let source = Source::new(vec![table_1, table_2]);
+
+    let query = r#"
+insert into pets(pet_id, pet_name) select id, name from animals as a returning pet_name;
+    "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false]);
+
+    let query = r#"
+insert into pets select id, tag from animals as a returning pet_name;
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true])
+}
+
+#[test]
+pub fn insert_on_conflict_returning() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true)
+        .push_column("nickname", true)
+        .push_column("owner", true);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+insert into pets(pet_id, pet_name, nickname) values (1, 'pet 1', 'p')
+on conflict (pet_id) do update set pet_name = excluded.pet_name, owner = 'me'
+returning pet_name, nickname, owner;
+    "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_name", "nickname", "owner"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, true]);
+
+    let query = r#"
+insert into pets(pet_id, pet_name) values (1, 'pet 1')
+on conflict (pet_id) do nothing
+returning pet_name, owner;
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullability(&["pet_name", "owner"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [Nullability::NotNull, Nullability::Nullable])
+}
+
+#[test]
+pub fn insert_on_conflict_sqlite() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let mut source = Source::new(vec![table_1]);
+    source.add_params(vec![false, true, false]);
+
+    let query = r#"
+insert into pets(pet_id, pet_name) values (?, ?)
+on conflict (pet_id) do update set pet_name = ?
+returning pet_name;
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true])
+}
@@ -1,6 +1,5 @@
 use nullable::{NullableState, Source, SqlFlavour, Table};
 
-
 #[test]
 pub fn one() {
     let orders_table = Table::new("vote")
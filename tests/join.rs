@@ -292,3 +292,90 @@ full outer join pets pets2 on pets2.pet_id = users.pet_id
     println!("{:?}", nullable);
     assert!(nullable == [true, true, true, true, true, true, true])
 }
+
+#[test]
+pub fn sqlite_join_without_constraint() {
+    let table_1 = Table::new("users")
+        .push_column("id", false)
+        .push_column("username", false)
+        .push_column("pet_id", true);
+
+    let table_2 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let source = Source::new(vec![table_1, table_2]);
+
+    let query = r#"
+select
+	users.id,
+	users.pet_id,
+	pets.pet_id,
+	pets.pet_name
+from
+	users
+join pets
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["id", "pet_id", "pet_id", "pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true, false, true])
+}
+
+#[test]
+pub fn sqlite_left_join_without_constraint() {
+    let table_1 = Table::new("users")
+        .push_column("id", false)
+        .push_column("username", false)
+        .push_column("pet_id", true);
+
+    let table_2 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", false);
+
+    let source = Source::new(vec![table_1, table_2]);
+
+    let query = r#"
+select
+	users.id,
+	users.username,
+	pets.pet_id,
+	pets.pet_name
+from
+	users
+left join pets
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["id", "username", "pet_id", "pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true, true])
+}
+
+#[test]
+pub fn postgres_join_without_constraint() {
+    let table_1 = Table::new("users")
+        .push_column("id", false)
+        .push_column("pet_id", true);
+
+    let table_2 = Table::new("pets").push_column("pet_id", false);
+
+    let source = Source::new(vec![table_1, table_2]);
+
+    let query = r#"
+select
+	users.id,
+	pets.pet_id
+from
+	users
+inner join pets
+    "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let error = state.try_get_nullable(&["id", "pet_id"]).unwrap_err();
+    println!("{:?}", error);
+    assert!(error
+        .to_string()
+        .starts_with("join without constraint is not supported in Postgres"))
+}
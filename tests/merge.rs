@@ -0,0 +1,76 @@
+use nullable::{NullableState, Source, SqlFlavour, Table};
+
+#[test]
+pub fn merge_without_returning() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("username", false)
+        .push_column("emailadres", true);
+    let staged_table = Table::new("staged_users")
+        .push_column("id", false)
+        .push_column("username", true)
+        .push_column("emailadres", true)
+        .push_column("deleted", false);
+
+    let source = Source::new(vec![users_table, staged_table]);
+
+    let query = r#"
+        merge into users u
+        using staged_users s on s.id = u.id
+        when matched and s.deleted then delete
+        when matched then update set username = coalesce(s.username, u.username), emailadres = $1
+        when not matched and s.username is not null then insert (id, username, emailadres) values (s.id, s.username, s.emailadres)
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&[]);
+    println!("{:?}", nullable);
+    assert!(nullable.is_empty())
+}
+
+#[test]
+pub fn merge_derived_source() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("username", false);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        merge into users u
+        using (select $1::int as id, $2::text as username) s on s.id = u.id
+        when matched then update set username = s.username
+        when not matched then insert values (s.id, s.username);
+
+        select id, username from users where id = $1
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "username"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false])
+}
+
+#[test]
+pub fn merge_unknown_column() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("username", false);
+    let staged_table = Table::new("staged_users")
+        .push_column("id", false)
+        .push_column("username", true);
+
+    let source = Source::new(vec![users_table, staged_table]);
+
+    // the clauses are analysed even though a `MERGE` has no columns.
+    let query = r#"
+        merge into users u
+        using staged_users s on s.id = u.id
+        when matched then update set username = s.nickname
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let err = state.try_get_nullable(&[]).unwrap_err();
+    println!("{err:?}");
+    assert!(err.to_string() == "Not found")
+}
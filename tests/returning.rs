@@ -1,4 +1,4 @@
-use nullable::{NullableState, Source, SqlFlavour, Table};
+use nullable::{Nullability, NullableState, Source, SqlFlavour, Table};
 
 #[test]
 pub fn returning_basic() {
@@ -59,3 +59,150 @@ pub fn returning_basic_3() {
     println!("{:?}", nullable);
     assert!(nullable == [false, true])
 }
+
+#[test]
+pub fn returning_update_from() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", true);
+    let pets_table = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("owner_id", true)
+        .push_column("pet_name", true)
+        .push_column("nickname", true);
+    let source = Source::new(vec![users_table, pets_table]);
+
+    let query = r#"
+        update users
+        set id = 1
+        from pets p
+        where p.owner_id = users.id and p.pet_name is not null
+        returning users.id, p.pet_id, p.pet_name, p.nickname
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "pet_id", "pet_name", "nickname"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, true])
+}
+
+#[test]
+pub fn returning_update_from_join() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", true);
+    let pets_table = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("owner_id", false);
+    let toys_table = Table::new("toys")
+        .push_column("toy_id", false)
+        .push_column("pet_id", false);
+    let source = Source::new(vec![users_table, pets_table, toys_table]);
+
+    let query = r#"
+        update users
+        set name = 'x'
+        from pets left join toys on toys.pet_id = pets.pet_id
+        where pets.owner_id = users.id
+        returning pets.pet_id, toys.toy_id
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_id", "toy_id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn returning_assigned_values() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("email", true)
+        .push_column("name", true)
+        .push_column("nickname", false)
+        .push_column("bio", true);
+    let mut source = Source::new(vec![users_table]);
+    source.add_params(vec![false, false]);
+
+    let query = r#"
+        update users
+        set email = $1, name = null, nickname = bio, bio = nickname
+        where id = $2
+        returning email, name, nickname, bio
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullability(&["email", "name", "nickname", "bio"]);
+    println!("{:?}", nullable);
+    assert!(
+        nullable
+            == [
+                Nullability::NotNull,
+                Nullability::AlwaysNull,
+                Nullability::NotNull,
+                Nullability::NotNull
+            ]
+    )
+}
+
+#[test]
+pub fn returning_assigned_where() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("email", true)
+        .push_column("name", true);
+    let mut source = Source::new(vec![users_table]);
+    source.add_params(vec![true]);
+
+    let query = r#"
+        update users
+        set email = ?, name = upper(name)
+        where name is not null
+        returning email, name
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["email", "name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, false])
+}
+
+#[test]
+pub fn returning_assigned_unknown() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("email", true);
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        update users
+        set email = some_unknown_fn(id)
+        where email is not null
+        returning email
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["email"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true])
+}
+
+#[test]
+pub fn returning_assigned_tuple() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("email", true)
+        .push_column("name", true);
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        update users
+        set (email, name) = ('a@b.c', null)
+        returning email, name
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullability(&["email", "name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [Nullability::NotNull, Nullability::AlwaysNull])
+}
@@ -0,0 +1,172 @@
+use nullable::{NullableState, Source, SqlFlavour, Table, View};
+
+#[test]
+pub fn qualified_table() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        select public.users.id, users.emailadres from public.users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "emailadres"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn table_in_schema() {
+    let users_table = Table::new("users")
+        .with_schema("auth")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+    let public_users_table = Table::new("users")
+        .push_column("id", true)
+        .push_column("emailadres", false);
+
+    let source = Source::new(vec![users_table, public_users_table]);
+
+    let query = r#"
+        select auth.users.id, auth.users.emailadres, u.id as public_id
+        from auth.users
+        inner join users u on u.emailadres = auth.users.emailadres
+        where u.id is not null and auth.users.emailadres is not null
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "emailadres", "public_id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false])
+}
+
+#[test]
+pub fn search_path() {
+    let auth_users_table = Table::new("users")
+        .with_schema("auth")
+        .push_column("id", false);
+    let app_users_table = Table::new("users")
+        .with_schema("app")
+        .push_column("id", true);
+
+    let mut source = Source::new(vec![auth_users_table, app_users_table]);
+    source.set_search_path(&["app", "auth"]);
+
+    let query = r#"
+        select id from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true])
+}
+
+#[test]
+pub fn sqlite_attached() {
+    let users_table = Table::new("users").push_column("id", false);
+    let logs_table = Table::new("logs")
+        .with_schema("archive")
+        .push_column("user_id", true);
+
+    let mut source = Source::new(vec![users_table, logs_table]);
+    source.attach("archive");
+
+    let query = r#"
+        select main.users.id, logs.user_id from main.users inner join logs on logs.user_id = users.id
+        where archive.logs.user_id is not null
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["id", "user_id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false])
+}
+
+#[test]
+pub fn search_path_without_schema() {
+    let users_table = Table::new("users").push_column("id", false);
+    let app_users_table = Table::new("users")
+        .with_schema("app")
+        .push_column("id", true);
+
+    let mut source = Source::new(vec![users_table, app_users_table]);
+    source.set_search_path(&["app"]);
+
+    let query = r#"
+        select id from users
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true]);
+
+    // tables without a schema are in `public`.
+    source.set_search_path(&["public", "app"]);
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false]);
+
+    // ctes shadow the search path.
+    let query = r#"
+        with users as (select 1 as id)
+        select id from users
+ "#;
+    source.set_search_path(&["app"]);
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
+}
+
+#[test]
+pub fn view_in_search_path() {
+    let users_table = Table::new("users")
+        .with_schema("app")
+        .push_column("id", false)
+        .push_column("email", true);
+
+    let mut source = Source::new(vec![users_table]);
+    source.push_view(
+        View::from_create_view(
+            "create view app.emails as select email from app.users",
+            SqlFlavour::Postgres,
+        )
+        .unwrap(),
+    );
+    source.set_search_path(&["app"]);
+
+    let query = r#"
+        select emails.email, app.emails.email as app_email from emails
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["email", "app_email"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, true])
+}
+
+#[test]
+pub fn table_in_catalog() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        select id from mydb.public.users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let err = state.try_get_nullable(&["id"]).unwrap_err();
+    println!("{err:?}");
+    assert!(err
+        .to_string()
+        .starts_with("names qualified by a catalog are not supported"))
+}
@@ -828,7 +828,14 @@ pub fn double_right_join_3() {
      "#;
 
     let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
-    let nullable = state.get_nullable(&["user_id", "name", "pet_id", "pet_name", "plant_id", "plant_name"]);
+    let nullable = state.get_nullable(&[
+        "user_id",
+        "name",
+        "pet_id",
+        "pet_name",
+        "plant_id",
+        "plant_name",
+    ]);
     println!("{:?}", nullable);
     assert!(nullable == [true, true, true, true, false, false])
 }
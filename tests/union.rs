@@ -1,4 +1,4 @@
-use nullable::{NullableState, Source, SqlFlavour};
+use nullable::{NullableState, Source, SqlFlavour, Table};
 
 #[test]
 pub fn union1() {
@@ -66,3 +66,111 @@ from
     println!("{:?}", nullable);
     assert!(nullable == [false, false])
 }
+
+#[test]
+pub fn intersect() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+    let owners_table = Table::new("owners")
+        .push_column("user_id", true)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table, owners_table]);
+
+    let query = r#"
+select id, emailadres from users
+intersect
+select user_id, emailadres from owners
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "emailadres"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn except() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+select id, emailadres from users
+except
+select null, emailadres from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "emailadres"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn union_left_most_names() {
+    let source = Source::empty();
+
+    let query = r#"
+select 1 as a, null as b
+union all
+select null as b, 2 as a
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["a", "b"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, true])
+}
+
+#[test]
+pub fn union_by_name() {
+    let source = Source::empty();
+
+    let query = r#"
+select 1 as a, null as b
+union all by name
+select 2 as b, 3 as a
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["a", "b"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn union_same_column_name() {
+    let user_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("name", false);
+    let pet_table = Table::new("pets")
+        .push_column("id", false)
+        .push_column("name", true);
+    let source = Source::new(vec![user_table, pet_table]);
+
+    let query = r#"
+select name from users
+union all
+select name from pets
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true]);
+
+    let query = r#"
+select name from pets
+union all
+select name from users
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true])
+}
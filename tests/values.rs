@@ -103,7 +103,6 @@ pub fn in_list_2() {
     assert!(nullable == [false])
 }
 
-
 #[test]
 pub fn is_distinct_from() {
     let source = Source::empty();
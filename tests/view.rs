@@ -0,0 +1,193 @@
+use nullable::{NullableState, Source, SqlFlavour, Table, View};
+
+#[test]
+pub fn view() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+    let pets_table = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("owner_id", false)
+        .push_column("pet_name", false);
+
+    let mut source = Source::new(vec![users_table, pets_table]);
+    source.add_view(
+        "user_pets",
+        "select users.id, users.emailadres, pets.pet_name from users left join pets on pets.owner_id = users.id",
+    );
+
+    let query = r#"
+        select id, emailadres, pet_name from user_pets where emailadres is not null
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "emailadres", "pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true])
+}
+
+#[test]
+pub fn create_view() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let mut source = Source::new(vec![users_table]);
+    source.push_view(
+        View::from_create_view(
+            "create view user_emails (user_id, email) as select id, coalesce(emailadres, '') from users",
+            SqlFlavour::Sqlite,
+        )
+        .unwrap(),
+    );
+    source.push_view(
+        View::from_create_view(
+            "create materialized view user_ids as select user_id from user_emails where user_id > 1",
+            SqlFlavour::Sqlite,
+        )
+        .unwrap(),
+    );
+
+    let query = r#"
+        select e.user_id, e.email, i.user_id as id from user_emails e left join user_ids i on i.user_id = e.user_id
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["user_id", "email", "id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true])
+}
+
+#[test]
+pub fn view_shadowed_by_cte() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let mut source = Source::new(vec![users_table]);
+    source.add_view("user_ids", "select id from users");
+
+    let query = r#"
+        with users as (select null as id, null as emailadres)
+        select user_ids.id from user_ids
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
+}
+
+#[test]
+pub fn create_view_in_script() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let source = Source::new(vec![users_table]);
+
+    let query = r#"
+        create view user_emails (user_id, email) as select id, emailadres from users where emailadres is not null;
+        create materialized view user_ids as select user_id from user_emails;
+        select e.user_id, e.email, i.user_id as id from user_emails e inner join user_ids i on i.user_id = e.user_id;
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["user_id", "email", "id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false])
+}
+
+#[test]
+pub fn view_references_itself() {
+    let users_table = Table::new("users").push_column("id", false);
+
+    let mut source = Source::new(vec![users_table]);
+    source.add_view("a", "select id from b");
+    source.add_view("b", "select id from a");
+
+    let query = r#"
+        select id from a
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let error = state.try_get_nullable(&["id"]).unwrap_err();
+    println!("{:?}", error);
+    assert!(error.to_string().starts_with("view references itself"))
+}
+
+#[test]
+pub fn view_after_catalog_change() {
+    let users_table = Table::new("users").push_column("email", true);
+
+    let mut source = Source::new(vec![users_table]);
+    source.add_view("emails", "select email from users");
+
+    let query = r#"
+        select email from emails
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["email"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true]);
+
+    source.push(Table::new("users").push_column("email", false));
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["email"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false]);
+}
+
+#[test]
+pub fn view_per_flavour() {
+    let users_table = Table::new("users").push_column("Email", false);
+
+    let mut source = Source::new(vec![users_table]);
+    source.add_view("emails", "select Email as address from users");
+
+    let query = r#"
+        select address from emails
+ "#;
+
+    // Sqlite matches `Email` case-insensitively, Postgres folds it to `email`.
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Sqlite);
+    let nullable = state.try_get_nullable(&["address"]);
+    println!("{:?}", nullable);
+    assert!(nullable.unwrap() == [false]);
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.try_get_nullable(&["address"]);
+    println!("{:?}", nullable);
+    assert!(nullable.is_err());
+}
+
+#[test]
+pub fn replace_view() {
+    let users_table = Table::new("users")
+        .push_column("id", false)
+        .push_column("emailadres", true);
+
+    let mut source = Source::new(vec![users_table]);
+    source.add_view("emails", "select emailadres as email from users");
+    source.add_view("user_emails", "select email from emails");
+
+    let query = r#"
+        select email from user_emails
+ "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["email"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true]);
+
+    // `user_emails` uses the replaced view.
+    source.add_view(
+        "emails",
+        "select coalesce(emailadres, '') as email from users",
+    );
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["email"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
+}
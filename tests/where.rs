@@ -1,4 +1,4 @@
-use nullable::{NullableState, Source, SqlFlavour, Table};
+use nullable::{Nullability, NullableState, Source, SqlFlavour, Table};
 
 #[test]
 pub fn where1() {
@@ -512,3 +512,410 @@ pub fn where13() {
     println!("{:?}", nullable);
     assert!(nullable == [false, false, true, true, false, false])
 }
+
+#[test]
+pub fn where14() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false)
+        .push_column("emailadres", true)
+        .push_column("age", true);
+
+    let orders_table = Table::new("agenda")
+        .push_column("agenda_id", false)
+        .push_column("startdate", false)
+        .push_column("user_id", false);
+
+    let source = Source::new(vec![user_table, orders_table]);
+
+    let query = r#"
+        select
+            a.agenda_id,
+            a.startdate,
+            u.user_id,
+			u.emailadres,
+            u.age
+        from
+            agenda a
+        left join
+            users u on a.user_id = u.user_id
+		where u.age in (1, 2)
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["agenda_id", "startdate", "user_id", "emailadres", "age"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, true, false])
+}
+
+#[test]
+pub fn where15() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false)
+        .push_column("emailadres", true)
+        .push_column("age", true);
+
+    let orders_table = Table::new("agenda")
+        .push_column("agenda_id", false)
+        .push_column("startdate", false)
+        .push_column("user_id", false);
+
+    let source = Source::new(vec![user_table, orders_table]);
+
+    let query = r#"
+        select
+            a.agenda_id,
+            a.startdate,
+            u.user_id,
+			u.emailadres,
+            u.age
+        from
+            agenda a
+        left join
+            users u on a.user_id = u.user_id
+		where not (u.emailadres is null)
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["agenda_id", "startdate", "user_id", "emailadres", "age"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, false, true])
+}
+
+#[test]
+pub fn where16() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false)
+        .push_column("emailadres", true)
+        .push_column("age", true);
+
+    let orders_table = Table::new("agenda")
+        .push_column("agenda_id", false)
+        .push_column("startdate", false)
+        .push_column("user_id", false);
+
+    let source = Source::new(vec![user_table, orders_table]);
+
+    let query = r#"
+        select
+            a.agenda_id,
+            a.startdate,
+            u.user_id,
+			u.emailadres,
+            u.age
+        from
+            agenda a
+        left join
+            users u on a.user_id = u.user_id
+		where (u.age = 1 or u.age = 2)
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["agenda_id", "startdate", "user_id", "emailadres", "age"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, true, false])
+}
+
+#[test]
+pub fn where17() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false)
+        .push_column("emailadres", true)
+        .push_column("age", true);
+
+    let orders_table = Table::new("agenda")
+        .push_column("agenda_id", false)
+        .push_column("startdate", false)
+        .push_column("user_id", false);
+
+    let source = Source::new(vec![user_table, orders_table]);
+
+    let query = r#"
+        select
+            a.agenda_id,
+            a.startdate,
+            u.user_id,
+			u.emailadres,
+            u.age
+        from
+            agenda a
+        left join
+            users u on a.user_id = u.user_id
+		where u.age = 1 or u.emailadres = 'mail'
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["agenda_id", "startdate", "user_id", "emailadres", "age"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, true, true, true])
+}
+
+#[test]
+pub fn where18() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false)
+        .push_column("emailadres", true)
+        .push_column("age", true);
+
+    let orders_table = Table::new("agenda")
+        .push_column("agenda_id", false)
+        .push_column("startdate", false)
+        .push_column("user_id", false);
+
+    let source = Source::new(vec![user_table, orders_table]);
+
+    let query = r#"
+        select
+            a.agenda_id,
+            a.startdate,
+            u.user_id,
+			u.emailadres,
+            u.age
+        from
+            agenda a
+        left join
+            users u on a.user_id = u.user_id
+		where u.age between 18 and 65 and u.emailadres like '%@%'
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["agenda_id", "startdate", "user_id", "emailadres", "age"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, false, false])
+}
+
+#[test]
+pub fn where19() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false)
+        .push_column("emailadres", true)
+        .push_column("age", true);
+
+    let orders_table = Table::new("agenda")
+        .push_column("agenda_id", false)
+        .push_column("startdate", false)
+        .push_column("user_id", false);
+
+    let source = Source::new(vec![user_table, orders_table]);
+
+    let query = r#"
+        select
+            a.agenda_id,
+            a.startdate,
+            u.user_id,
+			u.emailadres,
+            u.age
+        from
+            agenda a
+        left join
+            users u on a.user_id = u.user_id
+		where not (u.age is null or u.emailadres is null)
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["agenda_id", "startdate", "user_id", "emailadres", "age"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, false, false])
+}
+
+#[test]
+pub fn where_anti_join() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false)
+        .push_column("emailadres", true)
+        .push_column("age", true);
+
+    let orders_table = Table::new("agenda")
+        .push_column("agenda_id", false)
+        .push_column("startdate", false)
+        .push_column("user_id", false);
+
+    let source = Source::new(vec![user_table, orders_table]);
+
+    let query = r#"
+        select
+            a.agenda_id,
+            a.startdate,
+            u.user_id,
+			u.emailadres,
+            u.age
+        from
+            agenda a
+        left join
+            users u on a.user_id = u.user_id
+		where u.user_id is null
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable =
+        state.get_nullability(&["agenda_id", "startdate", "user_id", "emailadres", "age"]);
+    println!("{:?}", nullable);
+    assert!(
+        nullable
+            == [
+                Nullability::NotNull,
+                Nullability::NotNull,
+                Nullability::AlwaysNull,
+                Nullability::AlwaysNull,
+                Nullability::AlwaysNull
+            ]
+    )
+}
+
+#[test]
+pub fn where_is_null_nullable_column() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false)
+        .push_column("emailadres", true)
+        .push_column("age", true);
+
+    let orders_table = Table::new("agenda")
+        .push_column("agenda_id", false)
+        .push_column("startdate", false)
+        .push_column("user_id", false);
+
+    let source = Source::new(vec![user_table, orders_table]);
+
+    let query = r#"
+        select
+            a.agenda_id,
+            a.startdate,
+            u.user_id,
+			u.emailadres,
+            u.age
+        from
+            agenda a
+        left join
+            users u on a.user_id = u.user_id
+		where u.emailadres is null
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable =
+        state.get_nullability(&["agenda_id", "startdate", "user_id", "emailadres", "age"]);
+    println!("{:?}", nullable);
+    assert!(
+        nullable
+            == [
+                Nullability::NotNull,
+                Nullability::NotNull,
+                Nullability::Nullable,
+                Nullability::AlwaysNull,
+                Nullability::Nullable
+            ]
+    )
+}
+
+#[test]
+pub fn where_false() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            count(u.user_id),
+            max(u.name)
+        from
+            users u
+		where false
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["count", "max"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn where_constant_false() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            count(u.user_id),
+            max(u.name)
+        from
+            users u
+		where 1 = 0
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["count", "max"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn where_constant_true() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            count(u.user_id),
+            max(u.name)
+        from
+            users u
+		where 1 = 1
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["count", "max"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false])
+}
+
+#[test]
+pub fn where_constant_string() {
+    let user_table = Table::new("users")
+        .push_column("user_id", false)
+        .push_column("name", false);
+
+    let source = Source::new(vec![user_table]);
+
+    let query = r#"
+        select
+            count(u.user_id),
+            max(u.name)
+        from
+            users u
+		where 'true'
+     "#;
+
+    let mut state = NullableState::new(query, source.clone(), SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["count", "max"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false]);
+
+    // Sqlite casts `'1'` to the number 1.
+    let query = r#"
+        select
+            count(u.user_id),
+            max(u.name)
+        from
+            users u
+		where '1' or 0
+     "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["count", "max"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false])
+}
@@ -89,7 +89,6 @@ FROM
     assert!(nullable == [false, false])
 }
 
-
 #[test]
 pub fn with_5() {
     let source = Source::empty();
@@ -107,3 +106,265 @@ pub fn with_5() {
     println!("{:?}", nullable);
     assert!(nullable == [false])
 }
+
+#[test]
+pub fn with_recursive() {
+    let source = Source::empty();
+
+    let query = r#"
+with recursive numbers as (
+    select 1 as n
+    union all
+    select n + 1 from numbers where n < 10
+)
+select n from numbers
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["n"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
+}
+
+#[test]
+pub fn with_recursive_fixpoint() {
+    let table_1 = Table::new("employees")
+        .push_column("id", false)
+        .push_column("manager_id", true)
+        .push_column("name", false);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+with recursive chain as (
+    select id, name, id as top_id from employees where manager_id is null
+    union all
+    select e.id, e.name, c.top_id from employees e join chain c on e.manager_id = c.id
+)
+select id, name, top_id from chain
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "name", "top_id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false])
+}
+
+#[test]
+pub fn with_recursive_nullable() {
+    let table_1 = Table::new("employees")
+        .push_column("id", false)
+        .push_column("manager_id", true);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+with recursive chain as (
+    select id, id as parent from employees
+    union all
+    select c.parent, e.manager_id from chain c left join employees e on e.id = c.parent
+)
+select id, parent from chain
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "parent"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, true])
+}
+
+#[test]
+pub fn with_column_aliases() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+with named(id, name) as (
+    select pet_id, pet_name from pets
+)
+select id, name from named
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["id", "name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn with_recursive_column_aliases() {
+    let source = Source::empty();
+
+    let query = r#"
+with recursive numbers(n) as (
+    select 1
+    union all
+    select n + 1 from numbers where n < 10
+)
+select n from numbers
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["n"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
+}
+
+#[test]
+pub fn with_shadows_table() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+with pets as (
+    select pet_id, 'unknown' as pet_name from pets
+)
+select pet_id, pet_name from pets
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_id", "pet_name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false])
+}
+
+#[test]
+pub fn with_scoped_to_subquery() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+select s.pet_name as a, (select pet_name from pets limit 1) as b
+from (
+    with pets as (select pet_id, 'unknown' as pet_name from pets)
+    select pet_id, pet_name from pets
+) s
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, true])
+}
+
+#[test]
+pub fn with_shadows_outer_table() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+select
+    pet_name as a,
+    (with pets as (select 'unknown' as pet_name) select pet_name from pets) as b
+from pets
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a", "b"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true, false])
+}
+
+#[test]
+pub fn with_data_modifying() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+    let table_2 = Table::new("audit")
+        .push_column("audit_id", false)
+        .push_column("pet_id", true);
+
+    let source = Source::new(vec![table_1, table_2]);
+
+    let query = r#"
+with new_pets as (
+    insert into pets(pet_name) values ('pet 1') returning *
+), audited as (
+    insert into audit(pet_id) select pet_id from new_pets as n returning audit_id, pet_id as audited_id
+), renamed(id, name) as (
+    update pets set pet_name = 'pet 2' where pet_id = 1 returning pet_id, pet_name
+)
+select n.pet_id, a.audit_id, a.audited_id, r.name
+from new_pets n, audited a, renamed r
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_id", "audit_id", "audited_id", "name"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false, false, false])
+}
+
+#[test]
+pub fn with_data_modifying_alias() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let source = Source::new(vec![table_1]);
+
+    let query = r#"
+with new_pets as (
+    insert into pets as p (pet_name) values ('pet 1') returning p.pet_id
+)
+select * from new_pets
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["pet_id"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false])
+}
+
+#[test]
+pub fn with_data_modifying_params() {
+    let table_1 = Table::new("pets")
+        .push_column("pet_id", false)
+        .push_column("pet_name", true);
+
+    let mut source = Source::new(vec![table_1]);
+    source.add_params(vec![true, false]);
+
+    let query = r#"
+with new_pets as (
+    insert into pets(pet_name) values (?) returning pet_id
+)
+select pet_id, ? as p from new_pets
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Sqlite);
+    let nullable = state.get_nullable(&["pet_id", "p"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [false, false])
+}
+
+#[test]
+pub fn with_recursive_without_fixpoint() {
+    let source = Source::empty();
+
+    // every iteration nests the record one level deeper, the fields never settle.
+    let query = r#"
+with recursive nested(a) as (
+    select row(1)
+    union all
+    select row(a) from nested
+)
+select a from nested
+ "#;
+
+    let mut state = NullableState::new(query, source, SqlFlavour::Postgres);
+    let nullable = state.get_nullable(&["a"]);
+    println!("{:?}", nullable);
+    assert!(nullable == [true])
+}